@@ -0,0 +1,169 @@
+use std::ops::Range;
+
+use itertools::Itertools;
+
+/// One report cut out of a larger input, such as a teletype bulletin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawReport {
+    /// The report on a single line, with runs of whitespace collapsed to one
+    /// space and the `=` terminator removed.
+    pub text: String,
+    /// Byte range of the report in the original input, excluding the
+    /// terminator and surrounding whitespace.
+    pub span: Range<usize>,
+}
+
+impl RawReport {
+    fn new(input: &str, span: Range<usize>) -> Option<Self> {
+        let chunk = &input[span.clone()];
+        let start = span.start + (chunk.len() - chunk.trim_start().len());
+        let end = span.start + chunk.trim_end().len();
+        (start < end).then(|| RawReport {
+            text: input[start..end].split_whitespace().join(" "),
+            span: start..end,
+        })
+    }
+}
+
+/// `METAR ENGM 291820Z`, `COR ENGM 291820Z` or just `ENGM 291820Z`.
+fn starts_report(line: &str) -> bool {
+    let mut words = line
+        .split_whitespace()
+        .skip_while(|word| matches!(*word, "METAR" | "SPECI" | "TAF" | "COR" | "AMD"));
+    let is_icao = |word: &str| {
+        word.len() == 4
+            && word.starts_with(|c: char| c.is_ascii_uppercase())
+            && word
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    };
+    let is_time = |word: &str| {
+        word.strip_suffix('Z')
+            .is_some_and(|digits| digits.len() == 6 && digits.chars().all(|c| c.is_ascii_digit()))
+    };
+    matches!((words.next(), words.next()), (Some(icao), Some(time)) if is_icao(icao) && is_time(time))
+}
+
+/// WMO abbreviated heading, e.g. `SANO31 ENMI 291820` or
+/// `SANO31 ENMI 291820 CCA`.
+fn is_bulletin_heading(line: &str) -> bool {
+    let letters = |word: &str| word.chars().all(|c| c.is_ascii_uppercase());
+    let digits = |word: &str| word.chars().all(|c| c.is_ascii_digit());
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let [data_type, icao, time, rest @ ..] = words.as_slice() else {
+        return false;
+    };
+    data_type
+        .split_at_checked(4)
+        .is_some_and(|(letters_part, digits_part)| {
+            letters(letters_part) && digits_part.len() == 2 && digits(digits_part)
+        })
+        && icao.len() == 4
+        && letters(icao)
+        && time.len() == 6
+        && digits(time)
+        && match rest {
+            [] => true,
+            [bbb] => bbb.len() == 3 && letters(bbb),
+            _ => false,
+        }
+}
+
+/// Splits the input into reports.
+///
+/// Each report ends at its `=` terminator, or, if it has none, before the
+/// next line that starts a new report (`ENGM 291820Z ...`). Other lines,
+/// indented or not, continue the report. WMO bulletin headings are skipped.
+pub fn split_reports(input: &str) -> Vec<RawReport> {
+    let mut reports = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        if is_bulletin_heading(line) {
+            reports.extend(RawReport::new(input, start..line_start));
+            start = offset;
+            continue;
+        }
+        if !line.starts_with([' ', '\t']) && starts_report(line) {
+            reports.extend(RawReport::new(input, start..line_start));
+            start = line_start;
+        }
+        for (index, _) in line.match_indices('=') {
+            reports.extend(RawReport::new(input, start..line_start + index));
+            start = line_start + index + 1;
+        }
+    }
+    reports.extend(RawReport::new(input, start..input.len()));
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminated_and_wrapped() {
+        let input = "ENGM 291820Z 20005KT 9999\r\nFEW030 14/09 Q1000=\nENBR 291820Z 29008KT CAVOK 13/10 Q1019 =\n";
+        let reports = split_reports(input);
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| report.text.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "ENGM 291820Z 20005KT 9999 FEW030 14/09 Q1000",
+                "ENBR 291820Z 29008KT CAVOK 13/10 Q1019",
+            ]
+        );
+        assert_eq!(
+            &input[reports[1].span.clone()],
+            "ENBR 291820Z 29008KT CAVOK 13/10 Q1019"
+        );
+    }
+
+    #[test]
+    fn test_indented_continuation() {
+        let input = "ENGM 291820Z 20005KT 9999\n      FEW030 14/09 Q1000\n\nENBR 291820Z 29008KT CAVOK 13/10 Q1019";
+        let reports = split_reports(input);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(
+            reports[0].text,
+            "ENGM 291820Z 20005KT 9999 FEW030 14/09 Q1000"
+        );
+        assert_eq!(reports[1].span, 52..input.len());
+    }
+
+    #[test]
+    fn test_unterminated_after_terminated() {
+        let input = "ENBR 291820Z 29008KT CAVOK 13/10 Q1019=\nENZV 291820Z 31010KT 9999 FEW020 12/08 Q1018\n";
+        let reports = split_reports(input);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(
+            reports[1].text,
+            "ENZV 291820Z 31010KT 9999 FEW020 12/08 Q1018"
+        );
+    }
+
+    #[test]
+    fn test_bulletin_heading() {
+        let input =
+            "SANO31 ENMI 291820\r\nMETAR ENGM 291820Z 20005KT 9999\r\nFEW030 14/09 Q1000=\r\n";
+        let reports = split_reports(input);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(
+            reports[0].text,
+            "METAR ENGM 291820Z 20005KT 9999 FEW030 14/09 Q1000"
+        );
+        assert_eq!(
+            &input[reports[0].span.clone()],
+            "METAR ENGM 291820Z 20005KT 9999\r\nFEW030 14/09 Q1000"
+        );
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(split_reports(" \n=\n").is_empty());
+    }
+}
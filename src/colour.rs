@@ -0,0 +1,234 @@
+use std::fmt::{self, Display};
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::alphanumeric1,
+    combinator::{not, opt, value},
+    sequence::terminated,
+};
+
+use crate::{
+    obscuration::{Cloud, CloudCoverage, Obscuration, SkyCondition, Visibility},
+    optional_data::OptionalData,
+};
+
+/// Military aerodrome colour state, e.g. `WHT` or `BLACKAMB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColourState {
+    pub code: ColourCode,
+    /// `BLACK` prefix: the airfield is unusable for reasons other than
+    /// cloud and visibility.
+    pub black: bool,
+}
+
+/// Colour codes from best to worst, so they can be compared with `<`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColourCode {
+    Blue,
+    White,
+    Green,
+    Yellow1,
+    Yellow2,
+    Amber,
+    Red,
+}
+
+/// Minimum visibility in metres and cloud base in feet for each colour code.
+const COLOUR_LIMITS: [(ColourCode, u32, i32); 6] = [
+    (ColourCode::Blue, 8000, 2500),
+    (ColourCode::White, 5000, 1500),
+    (ColourCode::Green, 3700, 700),
+    (ColourCode::Yellow1, 2500, 500),
+    (ColourCode::Yellow2, 1600, 300),
+    (ColourCode::Amber, 800, 200),
+];
+
+const METERS_PER_STATUTE_MILE: f64 = 1609.344;
+
+impl ColourCode {
+    fn code(&self) -> &'static str {
+        match self {
+            ColourCode::Blue => "BLU",
+            ColourCode::White => "WHT",
+            ColourCode::Green => "GRN",
+            ColourCode::Yellow1 => "YLO1",
+            ColourCode::Yellow2 => "YLO2",
+            ColourCode::Amber => "AMB",
+            ColourCode::Red => "RED",
+        }
+    }
+
+    /// Colour code for a visibility in metres and a cloud base in feet. No
+    /// cloud base means no cloud that counts towards the colour state.
+    pub fn from_limits(visibility: u32, cloud_base: Option<i32>) -> ColourCode {
+        COLOUR_LIMITS
+            .iter()
+            .find(|(_, min_visibility, min_base)| {
+                visibility >= *min_visibility && cloud_base.is_none_or(|base| base >= *min_base)
+            })
+            .map_or(ColourCode::Red, |(code, _, _)| *code)
+    }
+}
+
+impl Display for ColourState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.black {
+            write!(f, "BLACK")?;
+        }
+        write!(f, "{}", self.code.code())
+    }
+}
+
+fn visibility_in_meters(visibility: &Visibility) -> Option<u32> {
+    match visibility {
+        Visibility::Meters(meters) => meters.prevailing.to_option(),
+        Visibility::StatuteMiles(miles) => {
+            let fraction = miles.fraction.map_or(0.0, |(numerator, denominator)| {
+                f64::from(numerator) / f64::from(denominator)
+            });
+            let miles = f64::from(miles.whole.unwrap_or(0)) + fraction;
+            Some((miles * METERS_PER_STATUTE_MILE) as u32)
+        }
+    }
+}
+
+/// Computes the colour code from the prevailing visibility and the lowest
+/// cloud layer that counts, which is the lowest layer of SCT or more. Returns
+/// `None` if the visibility or the cloud base is not reported.
+pub fn compute_colour_code(
+    visibility: &Visibility,
+    lowest_cloud: Option<&Cloud>,
+) -> Option<ColourCode> {
+    let visibility = visibility_in_meters(visibility)?;
+    let cloud_base = match lowest_cloud {
+        Some(cloud) => Some(cloud.height.clone().to_option()?.feet()),
+        None => None,
+    };
+    Some(ColourCode::from_limits(visibility, cloud_base))
+}
+
+/// Computes the colour code for the observed conditions. Vertical visibility
+/// is treated as the cloud base.
+pub fn compute_observed_colour_code(obscuration: &Obscuration) -> Option<ColourCode> {
+    let described = match obscuration {
+        Obscuration::Cavok => return Some(ColourCode::Blue),
+        Obscuration::Described(described) => described,
+    };
+    match &described.clouds {
+        SkyCondition::VerticalVisibility(height) => {
+            let visibility = visibility_in_meters(&described.visibility)?;
+            let base = height.clone().to_option()?.feet();
+            Some(ColourCode::from_limits(visibility, Some(base)))
+        }
+        clouds => {
+            let lowest_cloud = clouds
+                .layers()
+                .iter()
+                .filter(|cloud| {
+                    matches!(
+                        cloud.coverage,
+                        OptionalData::Data(
+                            CloudCoverage::Scattered
+                                | CloudCoverage::Broken
+                                | CloudCoverage::Overcast
+                        )
+                    )
+                })
+                .min_by_key(|cloud| cloud.height.clone().to_option().map(|h| h.height));
+            compute_colour_code(&described.visibility, lowest_cloud)
+        }
+    }
+}
+
+fn nom_colour_code(input: &str) -> IResult<&str, ColourCode> {
+    alt((
+        value(ColourCode::Blue, tag("BLU")),
+        value(ColourCode::White, tag("WHT")),
+        value(ColourCode::Green, tag("GRN")),
+        value(ColourCode::Yellow1, tag("YLO1")),
+        value(ColourCode::Yellow2, tag("YLO2")),
+        value(ColourCode::Amber, tag("AMB")),
+        value(ColourCode::Red, tag("RED")),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_colour_state(input: &str) -> IResult<&str, ColourState> {
+    terminated((opt(tag("BLACK")), nom_colour_code), not(alphanumeric1))
+        .map(|(black, code)| ColourState {
+            code,
+            black: black.is_some(),
+        })
+        .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        obscuration::{MetersVisibility, nom_obscuration, nom_obscuration_and_weather},
+        units::altitudes::CloudHeight,
+    };
+
+    #[test]
+    fn test_parse_colour_state() {
+        assert_eq!(
+            nom_colour_state("BLACKGRN"),
+            Ok((
+                "",
+                ColourState {
+                    code: ColourCode::Green,
+                    black: true
+                }
+            ))
+        );
+        assert_eq!(
+            nom_colour_state("YLO1 NOSIG").unwrap().1.to_string(),
+            "YLO1"
+        );
+        assert!(nom_colour_state("REDZRA").is_err());
+    }
+
+    #[test]
+    fn test_compute_colour_code() {
+        let visibility = Visibility::Meters(MetersVisibility::new(OptionalData::Data(9999)));
+        assert_eq!(
+            compute_colour_code(&visibility, None),
+            Some(ColourCode::Blue)
+        );
+        let cloud = Cloud {
+            coverage: OptionalData::Data(CloudCoverage::Broken),
+            height: OptionalData::Data(CloudHeight { height: 6 }),
+            cloud_type: None,
+        };
+        assert_eq!(
+            compute_colour_code(&visibility, Some(&cloud)),
+            Some(ColourCode::Yellow1)
+        );
+        let visibility = Visibility::Meters(MetersVisibility::new(OptionalData::Data(700)));
+        assert_eq!(
+            compute_colour_code(&visibility, None),
+            Some(ColourCode::Red)
+        );
+    }
+
+    #[test]
+    fn test_observed_colour_code() {
+        let (_, obscuration) = nom_obscuration("6000 FEW003 SCT016 BKN030").unwrap();
+        assert_eq!(
+            compute_observed_colour_code(&obscuration),
+            Some(ColourCode::White)
+        );
+        let (_, (obscuration, _)) = nom_obscuration_and_weather("0300 FG VV001").unwrap();
+        assert_eq!(
+            compute_observed_colour_code(&obscuration),
+            Some(ColourCode::Red)
+        );
+        assert_eq!(
+            compute_observed_colour_code(&Obscuration::Cavok),
+            Some(ColourCode::Blue)
+        );
+    }
+}
@@ -1,7 +1,17 @@
+pub mod bulletin;
+pub mod colour;
 pub mod metar;
 pub mod obscuration;
 pub mod optional_data;
 pub mod pressure;
+pub mod remarks;
+pub mod runway;
+pub mod sea;
+pub mod sigmet;
+pub mod taf;
 pub mod temprature;
+pub mod tolerant;
+pub mod trend;
 pub mod units;
+pub mod weather;
 pub mod wind;
@@ -1,87 +1,427 @@
-use std::io::{BufRead, BufReader, Read};
+use std::{io::Read, ops::Range};
 
-use itertools::Itertools;
 use nom::{
     AsChar, Finish, IResult, Parser,
-    bytes::complete::{tag, take_till},
-    character::complete::char,
-    combinator::opt,
-    sequence::preceded,
+    branch::alt,
+    bytes::complete::{tag, take_till, take_while_m_n},
+    character::complete::{char, satisfy},
+    combinator::{all_consuming, opt, recognize, value},
+    multi::many0,
+    sequence::{preceded, terminated},
 };
+use thiserror::Error;
 
 use crate::{
-    obscuration::{Obscuration, nom_obscuration},
+    bulletin::split_reports,
+    colour::{ColourState, nom_colour_state},
+    obscuration::{Obscuration, nom_obscuration_and_weather},
     pressure::{Pressure, nom_pressure},
+    remarks::{Remarks, parse_remarks},
+    runway::{RunwayState, WindShear, nom_runway_state, nom_wind_shear},
+    sea::{SeaState, nom_sea_state},
     temprature::{Temprature, nom_temprature},
+    trend::{Trend, nom_trend},
     units::timestamp::{Timestamp, nom_metar_timestamp},
+    weather::{PresentWeather, RecentWeather, nom_recent_weather},
     wind::{Wind, nom_wind},
 };
 
 #[derive(Debug, Clone)]
 pub struct Metar {
     pub raw: String,
+    pub report_type: ReportType,
+    pub correction: Option<ReportModifier>,
     pub icao: String,
     pub timestamp: Timestamp,
     pub auto: bool,
     pub wind: Wind,
     pub obscuration: Obscuration,
+    pub weather: Vec<PresentWeather>,
     pub temprature: Temprature,
     pub pressure: Pressure,
+    pub recent_weather: Vec<RecentWeather>,
+    pub wind_shear: Option<WindShear>,
+    pub sea_state: Option<SeaState>,
+    pub runway_state: Vec<RunwayState>,
+    pub colour_state: Option<ColourState>,
     pub nosig: bool,
-    pub remarks: Option<String>,
+    pub trends: Vec<Trend>,
+    pub remarks: Option<Remarks>,
+    /// Groups skipped by [`parse_metar_tolerant`](crate::tolerant::parse_metar_tolerant).
+    /// Always empty for the strict parsers.
+    pub unparsed: Vec<UnparsedGroup>,
 }
 
-pub fn nom_parse_metar(input: &str) -> IResult<&str, Metar> {
-    let (rest, (icao, timestamp, auto, wind, obscuration, temprature, pressure, nosig, remark)) = (
-        nom::bytes::complete::take(4usize),
-        preceded(char(' '), nom_metar_timestamp),
+/// A group that could not be recognised, with its byte range in the report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnparsedGroup {
+    pub text: String,
+    pub span: Range<usize>,
+}
+
+/// `NIL`: the report was expected but is missing. Only the header is known.
+#[derive(Debug, Clone)]
+pub struct NilReport {
+    pub raw: String,
+    pub report_type: ReportType,
+    pub correction: Option<ReportModifier>,
+    pub icao: String,
+    pub timestamp: Timestamp,
+}
+
+#[derive(Debug, Clone)]
+pub enum MetarReport {
+    Observed(Box<Metar>),
+    Nil(NilReport),
+}
+
+/// Reports without a `METAR`/`SPECI` prefix are treated as routine reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportType {
+    #[default]
+    Metar,
+    /// Special report, issued when conditions change significantly between
+    /// routine reports.
+    Speci,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportModifier {
+    Corrected,
+    Amended,
+}
+
+struct Header {
+    report_type: ReportType,
+    correction: Option<ReportModifier>,
+    icao: String,
+    timestamp: Timestamp,
+}
+
+pub(crate) fn nom_icao(input: &str) -> IResult<&str, &str> {
+    recognize((
+        satisfy(|c| c.is_ascii_uppercase()),
+        take_while_m_n(3, 3, |c: char| c.is_ascii_uppercase() || c.is_ascii_digit()),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_report_type(input: &str) -> IResult<&str, ReportType> {
+    alt((
+        value(ReportType::Metar, tag("METAR")),
+        value(ReportType::Speci, tag("SPECI")),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_report_modifier(input: &str) -> IResult<&str, ReportModifier> {
+    alt((
+        value(ReportModifier::Corrected, tag("COR")),
+        value(ReportModifier::Amended, tag("AMD")),
+    ))
+    .parse(input)
+}
+
+/// The modifier is placed after the report type by some sources and after the
+/// timestamp by others, so both positions are accepted.
+fn nom_header(input: &str) -> IResult<&str, Header> {
+    let (rest, report_type) = opt(terminated(nom_report_type, char(' '))).parse(input)?;
+    let (rest, leading_modifier) = opt(terminated(nom_report_modifier, char(' '))).parse(rest)?;
+    let (rest, (icao, timestamp)) =
+        (nom_icao, preceded(char(' '), nom_metar_timestamp)).parse(rest)?;
+    let (rest, trailing_modifier) = opt(preceded(char(' '), nom_report_modifier)).parse(rest)?;
+    Ok((
+        rest,
+        Header {
+            report_type: report_type.unwrap_or_default(),
+            correction: leading_modifier.or(trailing_modifier),
+            icao: icao.to_string(),
+            timestamp,
+        },
+    ))
+}
+
+fn nom_metar_body<'a>(input: &'a str, rest: &'a str, header: Header) -> IResult<&'a str, Metar> {
+    let Header {
+        report_type,
+        correction,
+        icao,
+        timestamp,
+    } = header;
+    let (rest, (auto, wind, (obscuration, weather), temprature, pressure)) = (
         opt(tag(" AUTO")),
         preceded(char(' '), nom_wind),
-        preceded(char(' '), nom_obscuration),
+        preceded(char(' '), nom_obscuration_and_weather),
         preceded(char(' '), nom_temprature),
         preceded(char(' '), nom_pressure),
-        opt(tag(" NOSIG")),
-        opt(preceded(tag(" RMK "), take_till(char::is_newline))),
     )
-        .parse(input)?;
+        .parse(rest)?;
+    let (rest, recent_weather) = many0(preceded(char(' '), nom_recent_weather)).parse(rest)?;
+    let (rest, wind_shear) = opt(preceded(char(' '), nom_wind_shear)).parse(rest)?;
+    let (rest, sea_state) = opt(preceded(char(' '), nom_sea_state)).parse(rest)?;
+    let (rest, runway_state) = many0(preceded(char(' '), nom_runway_state)).parse(rest)?;
+    let (rest, colour_state) = opt(preceded(char(' '), nom_colour_state)).parse(rest)?;
+    let (rest, nosig) = opt(tag(" NOSIG")).parse(rest)?;
+    let (rest, trends) = many0(preceded(char(' '), |i| nom_trend(i, &timestamp))).parse(rest)?;
+    let (rest, remark) = opt(preceded(tag(" RMK "), take_till(char::is_newline))).parse(rest)?;
+    let remarks = remark.map(|remark| parse_remarks(remark, &timestamp));
     Ok((
         rest,
         Metar {
             raw: input.to_string(),
-            icao: icao.to_string(),
+            report_type,
+            correction,
+            icao,
             timestamp,
             auto: auto.is_some(),
             wind,
             obscuration,
+            weather,
             temprature,
             pressure,
+            recent_weather,
+            wind_shear,
+            sea_state,
+            runway_state,
+            colour_state,
             nosig: nosig.is_some(),
-            remarks: remark.map(str::to_string),
+            trends,
+            remarks,
+            unparsed: Vec::new(),
         },
     ))
 }
 
-fn parse_metars<R: Read>(input: R) -> Result<Vec<(String, Metar)>, nom::error::Error<String>> {
-    let reader = BufReader::new(input);
-    let results = reader
-        .lines()
-        .filter_map(Result::ok)
-        .map(|m| -> Result<(String, Metar), nom::error::Error<String>> {
-            let (rest, metar) = nom_parse_metar(&m).finish()?;
-            Ok((rest.to_string(), metar))
+/// Parses an observed report. `NIL` reports are rejected, use
+/// [`nom_parse_report`] to accept those as well.
+pub fn nom_parse_metar(input: &str) -> IResult<&str, Metar> {
+    let (rest, header) = nom_header(input)?;
+    nom_metar_body(input, rest, header)
+}
+
+pub fn nom_parse_report(input: &str) -> IResult<&str, MetarReport> {
+    let (rest, header) = nom_header(input)?;
+    if let Ok((rest, _)) = tag::<_, _, nom::error::Error<&str>>(" NIL").parse(rest) {
+        let Header {
+            report_type,
+            correction,
+            icao,
+            timestamp,
+        } = header;
+        return Ok((
+            rest,
+            MetarReport::Nil(NilReport {
+                raw: input.to_string(),
+                report_type,
+                correction,
+                icao,
+                timestamp,
+            }),
+        ));
+    }
+    nom_metar_body(input, rest, header)
+        .map(|(rest, metar)| (rest, MetarReport::Observed(Box::new(metar))))
+}
+
+#[derive(Debug, Error)]
+pub enum ReportsParseError {
+    #[error("Read error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Parse error: Report at {span:?}: {error}")]
+    Report {
+        /// Byte range of the report in the input.
+        span: Range<usize>,
+        error: nom::error::Error<String>,
+    },
+}
+
+/// Parses every report in the input, see [`split_reports`] for how reports
+/// are separated. Each report is returned with its byte range in the input.
+/// Fails if any report is not parsed completely.
+pub fn parse_metars<R: Read>(
+    mut input: R,
+) -> Result<Vec<(Range<usize>, MetarReport)>, ReportsParseError> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    split_reports(&text)
+        .into_iter()
+        .map(|report| {
+            let (_, parsed) = all_consuming(nom_parse_report)
+                .parse(&report.text)
+                .finish()
+                .map_err(|error| ReportsParseError::Report {
+                    span: report.span.clone(),
+                    error: nom::error::Error::new(error.input.to_string(), error.code),
+                })?;
+            Ok((report.span, parsed))
         })
-        .try_collect();
-    results
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        colour::{ColourCode, compute_observed_colour_code},
+        optional_data::OptionalData,
+        sea::SeaSurface,
+        trend::TrendChange,
+    };
 
     #[test]
     fn test_parse() {
         let input = std::fs::File::open("test.metars").unwrap();
         let metars = parse_metars(input).unwrap();
-        assert!(metars.len() > 0);
+        assert!(!metars.is_empty());
+    }
+
+    #[test]
+    fn test_parse_bulletin() {
+        let input = "METAR ENGM 291820Z 20005KT 9999\nFEW030 14/09 Q1000=\nENBR 291820Z NIL=\n";
+        let metars = parse_metars(input.as_bytes()).unwrap();
+        assert_eq!(metars.len(), 2);
+        assert!(matches!(metars[0].1, MetarReport::Observed(_)));
+        assert!(matches!(metars[1].1, MetarReport::Nil(_)));
+        assert_eq!(metars[1].0, 52..68);
+    }
+
+    #[test]
+    fn test_parse_bulletin_mixed_terminators() {
+        let input = "SANO31 ENMI 291820\r\nENBR 291820Z 29008KT CAVOK 13/10 Q1019=\r\nENZV 291820Z 31010KT 9999 FEW020 12/08 Q1018\r\n";
+        let metars = parse_metars(input.as_bytes()).unwrap();
+        assert_eq!(metars.len(), 2);
+        assert_eq!(
+            &input[metars[1].0.clone()],
+            "ENZV 291820Z 31010KT 9999 FEW020 12/08 Q1018"
+        );
+    }
+
+    #[test]
+    fn test_parse_bulletin_leftover_text() {
+        let input = "ENBR 291820Z 29008KT CAVOK 13/10 Q1019 GARBAGE=\n";
+        let Err(ReportsParseError::Report { span, .. }) = parse_metars(input.as_bytes()) else {
+            panic!("expected a report error");
+        };
+        assert_eq!(span, 0..46);
+    }
+
+    #[test]
+    fn test_recent_weather() {
+        let input = "ENVA 291820Z 27028KT 9999 -DZ BKN019 OVC030 12/09 Q1009 REDZRA RESN RMK WIND 670FT 28027G45KT";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            metar
+                .recent_weather
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["REDZRA", "RESN"]
+        );
+        let remarks = metar.remarks.unwrap();
+        assert_eq!(remarks.raw, "WIND 670FT 28027G45KT");
+        assert_eq!(remarks.winds.len(), 1);
+        assert!(remarks.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_wind_shear() {
+        let input = "EKCH 291820Z 24015KT 9999 FEW020 15/10 Q1010 RETSRA WS R22L NOSIG";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            metar.wind_shear,
+            Some(WindShear::Runways(vec!["22L".to_string()]))
+        );
+        assert!(metar.nosig);
+    }
+
+    #[test]
+    fn test_sea_state() {
+        let input = "ENUN 291820Z AUTO 30021KT //// OVC006/// 10/09 Q1004 W10/S4";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        let sea_state = metar.sea_state.unwrap();
+        assert_eq!(sea_state.temperature, OptionalData::Data(10));
+        assert_eq!(
+            sea_state.surface,
+            OptionalData::Data(SeaSurface::StateOfSea(OptionalData::Data(4)))
+        );
+    }
+
+    #[test]
+    fn test_runway_state() {
+        let input =
+            "ENGM 291820Z 01005KT 9999 -SN BKN012 M03/M05 Q0998 R01L/490295 R19R/CLRD95 NOSIG";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(metar.runway_state.len(), 2);
+        assert!(metar.nosig);
+    }
+
+    #[test]
+    fn test_colour_state() {
+        let input = "EGVN 291820Z 27015KT 9999 FEW030 15/08 Q1012 BLU TEMPO 4000 SHRA BKN010 GRN";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(metar.colour_state.map(|c| c.code), Some(ColourCode::Blue));
+        assert_eq!(
+            metar.trends[0].colour_state.map(|c| c.code),
+            Some(ColourCode::Green)
+        );
+        assert_eq!(
+            compute_observed_colour_code(&metar.obscuration),
+            Some(ColourCode::Blue)
+        );
+    }
+
+    #[test]
+    fn test_trends() {
+        let input = "ENOL 291820Z 28026KT 9999 SCT015 BKN035 11/08 Q1010 BECMG 24010KT TEMPO 26030G42KT 3000 SHRA BKN012CB";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        assert!(!metar.nosig);
+        assert_eq!(
+            metar.trends.iter().map(|t| t.change).collect::<Vec<_>>(),
+            vec![TrendChange::Becoming, TrendChange::Temporary]
+        );
+    }
+
+    #[test]
+    fn test_report_header() {
+        let input = "SPECI COR ENGM 291820Z 30008KT CAVOK 19/03 Q1009";
+        let (rest, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(metar.report_type, ReportType::Speci);
+        assert_eq!(metar.correction, Some(ReportModifier::Corrected));
+        assert_eq!(metar.icao, "ENGM");
+
+        let input = "METAR ENGM 291820Z COR AUTO 30008KT CAVOK 19/03 Q1009";
+        let (_, metar) = nom_parse_metar(input).unwrap();
+        assert_eq!(metar.report_type, ReportType::Metar);
+        assert_eq!(metar.correction, Some(ReportModifier::Corrected));
+        assert!(metar.auto);
+
+        let (_, metar) = nom_parse_metar("ENGM 291820Z 30008KT CAVOK 19/03 Q1009").unwrap();
+        assert_eq!(metar.report_type, ReportType::Metar);
+        assert_eq!(metar.correction, None);
+    }
+
+    #[test]
+    fn test_nil_report() {
+        let input = "METAR ENXX 291820Z NIL";
+        assert!(nom_parse_metar(input).is_err());
+        let (rest, report) = nom_parse_report(input).unwrap();
+        assert_eq!(rest, "");
+        let MetarReport::Nil(nil) = report else {
+            panic!("expected NIL report");
+        };
+        assert_eq!(nil.icao, "ENXX");
+        assert_eq!(nil.timestamp.to_string(), "291820Z");
+    }
+
+    #[test]
+    fn test_invalid_icao() {
+        assert!(nom_parse_report("en 291820Z NIL").is_err());
     }
 }
@@ -2,15 +2,20 @@ use nom::{
     Parser,
     branch::alt,
     bytes::complete::{tag, take},
-    character::complete::{alphanumeric1, u32},
-    combinator::{all_consuming, map, map_parser, opt, value},
-    multi::many0,
+    character::complete::{alphanumeric1, char, u32},
+    combinator::{all_consuming, map, map_parser, not, opt, success, value},
+    multi::{many0, separated_list1},
     sequence::{preceded, separated_pair, terminated},
 };
 
 use crate::{
     optional_data::OptionalData,
-    units::altitudes::{CloudHeight, nom_cloud_height},
+    runway::nom_runway_designator,
+    units::{
+        altitudes::{CloudHeight, nom_cloud_height},
+        compass::{CompassOctant, nom_compass_octant},
+    },
+    weather::{PresentWeather, nom_present_weather},
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -23,15 +28,59 @@ pub enum Obscuration {
 pub struct DescribedObscuration {
     pub visibility: Visibility,
     pub rvr: Vec<Rvr>,
-    pub clouds: Vec<Cloud>,
+    pub clouds: SkyCondition,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SkyCondition {
+    /// Reported cloud layers. Empty if no cloud group was reported.
+    Clouds(Vec<Cloud>),
+    /// `VV`: sky obscured, with the vertical visibility.
+    VerticalVisibility(OptionalData<CloudHeight, 3>),
+    /// `NSC`: no cloud below 5000 ft or the highest minimum sector altitude,
+    /// and no CB or TCU.
+    NoSignificantCloud,
+    /// `NCD`: automatic station detected no cloud.
+    NoCloudDetected,
+    /// `SKC`: sky clear.
+    SkyClear,
+    /// `CLR`: automatic station detected no cloud below 12000 ft.
+    ClearBelow12000Ft,
+}
+
+impl SkyCondition {
+    /// Cloud layers, if any are reported.
+    pub fn layers(&self) -> &[Cloud] {
+        match self {
+            SkyCondition::Clouds(clouds) => clouds,
+            _ => &[],
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Rvr {
     pub runway: String,
+    /// The RVR, or the lower bound when it is varying.
+    pub value: RvrValue,
+    /// Upper bound of a varying RVR, e.g. `1200` in `R24/0600V1200U`.
+    pub variable_max: Option<RvrValue>,
+    pub unit: RvrUnit,
+    pub comment: Option<Trend>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RvrValue {
     pub value: OptionalData<u32, 4>,
+    /// `M`/`P`: the RVR is below or above what the system can assess.
     pub distance_modifier: Option<DistanceModifier>,
-    pub comment: Option<Trend>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RvrUnit {
+    Meters,
+    /// `FT` suffix, used in North America.
+    Feet,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -45,7 +94,15 @@ pub enum Trend {
 pub struct Cloud {
     pub coverage: OptionalData<CloudCoverage, 3>,
     pub height: OptionalData<CloudHeight, 3>,
-    pub cloud_type: Option<OptionalData<String, 3>>,
+    pub cloud_type: Option<OptionalData<CloudType, 3>>,
+}
+
+/// Convective cloud type. Automatic stations report `///` when they cannot
+/// determine whether convective cloud is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudType {
+    Cumulonimbus,
+    ToweringCumulus,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -58,10 +115,36 @@ pub enum CloudCoverage {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Visibility {
-    Meters(OptionalData<u32, 4>),
+    Meters(MetersVisibility),
     StatuteMiles(StatuteMilesVisibility),
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MetersVisibility {
+    pub prevailing: OptionalData<u32, 4>,
+    /// `NDV`: the station cannot report directional variations.
+    pub no_directional_variation: bool,
+    pub minimum: Option<DirectionalVisibility>,
+}
+
+/// Minimum visibility reported in a second group, e.g. `1500SW`, when it
+/// differs significantly from the prevailing visibility.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DirectionalVisibility {
+    pub meters: u32,
+    pub direction: CompassOctant,
+}
+
+impl MetersVisibility {
+    pub fn new(prevailing: OptionalData<u32, 4>) -> Self {
+        MetersVisibility {
+            prevailing,
+            no_directional_variation: false,
+            minimum: None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct StatuteMilesVisibility {
     pub whole: Option<u32>,
@@ -75,35 +158,45 @@ pub enum DistanceModifier {
     GreaterThan,
 }
 
-pub(crate) fn nom_obscuration(input: &str) -> nom::IResult<&str, Obscuration> {
+pub(crate) fn nom_visibility(input: &str) -> nom::IResult<&str, Visibility> {
     alt((
-        value(Obscuration::Cavok, tag("CAVOK")),
-        map(nom_described_obscuration, Obscuration::Described),
+        map(nom_statute_miles_visibility, Visibility::StatuteMiles),
+        map(nom_meters_visibility, Visibility::Meters),
     ))
     .parse(input)
 }
 
-fn nom_described_obscuration(input: &str) -> nom::IResult<&str, DescribedObscuration> {
+fn nom_meters_visibility(input: &str) -> nom::IResult<&str, MetersVisibility> {
     map(
-        (nom_visibility, many0(nom_rvr), many0(nom_cloud)),
-        |(visibility, rvr, clouds)| DescribedObscuration {
-            visibility,
-            rvr,
-            clouds,
+        (
+            OptionalData::optional_field(map_parser(take(4usize), all_consuming(u32))),
+            terminated(opt(tag("NDV")), not(alphanumeric1)),
+            opt(preceded(char(' '), nom_directional_visibility)),
+        ),
+        |(prevailing, ndv, minimum)| MetersVisibility {
+            prevailing,
+            no_directional_variation: ndv.is_some(),
+            minimum,
         },
     )
     .parse(input)
 }
 
-fn nom_visibility(input: &str) -> nom::IResult<&str, Visibility> {
-    alt((
-        map(nom_statute_miles_visibility, Visibility::StatuteMiles),
-        map(OptionalData::optional_field(map_parser(take(4usize), all_consuming(u32))), Visibility::Meters),
-    ))
+fn nom_directional_visibility(input: &str) -> nom::IResult<&str, DirectionalVisibility> {
+    map(
+        terminated(
+            (
+                map_parser(take(4usize), all_consuming(u32)),
+                nom_compass_octant,
+            ),
+            not(alphanumeric1),
+        ),
+        |(meters, direction)| DirectionalVisibility { meters, direction },
+    )
     .parse(input)
 }
 
-fn nom_fraction(input: &str) -> nom::IResult<&str, (u32, u32)> {
+pub(crate) fn nom_fraction(input: &str) -> nom::IResult<&str, (u32, u32)> {
     separated_pair(u32, tag("/"), u32).parse(input)
 }
 
@@ -139,7 +232,7 @@ fn nom_statute_miles_visibility(input: &str) -> nom::IResult<&str, StatuteMilesV
     alt((fraction_only, whole)).parse(input)
 }
 
-fn nom_distance_modifier(input: &str) -> nom::IResult<&str, DistanceModifier> {
+pub(crate) fn nom_distance_modifier(input: &str) -> nom::IResult<&str, DistanceModifier> {
     alt((
         value(DistanceModifier::LessThan, tag("M")),
         value(DistanceModifier::GreaterThan, tag("P")),
@@ -147,16 +240,31 @@ fn nom_distance_modifier(input: &str) -> nom::IResult<&str, DistanceModifier> {
     .parse(input)
 }
 
-fn nom_rvr(input: &str) -> nom::IResult<&str, Rvr> {
+fn nom_rvr_value(input: &str) -> nom::IResult<&str, RvrValue> {
+    map(
+        (
+            opt(nom_distance_modifier),
+            OptionalData::optional_field(map_parser(take(4usize), all_consuming(u32))),
+        ),
+        |(distance_modifier, value)| RvrValue {
+            value,
+            distance_modifier,
+        },
+    )
+    .parse(input)
+}
+
+pub(crate) fn nom_rvr(input: &str) -> nom::IResult<&str, Rvr> {
     map(
         preceded(
             tag("R"),
             separated_pair(
-                alphanumeric1,
+                nom_runway_designator,
                 tag("/"),
                 (
-                    opt(nom_distance_modifier),
-                    OptionalData::optional_field(map_parser(take(4usize), all_consuming(u32))),
+                    nom_rvr_value,
+                    opt(preceded(tag("V"), nom_rvr_value)),
+                    opt(value(RvrUnit::Feet, tag("FT"))),
                     opt(alt((
                         value(Trend::Decreasing, tag("D")),
                         value(Trend::Increasing, tag("U")),
@@ -165,10 +273,11 @@ fn nom_rvr(input: &str) -> nom::IResult<&str, Rvr> {
                 ),
             ),
         ),
-        |(runway, (distance_modifier, value, comment))| Rvr {
-            runway: runway.to_string(),
+        |(runway, (value, variable_max, unit, comment))| Rvr {
+            runway,
             value,
-            distance_modifier,
+            variable_max,
+            unit: unit.unwrap_or(RvrUnit::Meters),
             comment,
         },
     )
@@ -185,14 +294,18 @@ fn nom_cloud_coverage(input: &str) -> nom::IResult<&str, OptionalData<CloudCover
     .parse(input)
 }
 
-fn nom_cloud_type(input: &str) -> nom::IResult<&str, OptionalData<String, 3>> {
-    OptionalData::optional_field(map(alphanumeric1, |s: &str| s.to_string())).parse(input)
+fn nom_cloud_type(input: &str) -> nom::IResult<&str, OptionalData<CloudType, 3>> {
+    OptionalData::optional_field(alt((
+        value(CloudType::Cumulonimbus, tag("CB")),
+        value(CloudType::ToweringCumulus, tag("TCU")),
+    )))
+    .parse(input)
 }
 
-fn nom_cloud(input: &str) -> nom::IResult<&str, Cloud> {
+pub(crate) fn nom_cloud(input: &str) -> nom::IResult<&str, Cloud> {
     let (input, coverage) = nom_cloud_coverage.parse(input)?;
     let (input, height) = nom_cloud_height.parse(input)?;
-    let (input, cloud_type) = opt(nom_cloud_type).parse(input)?;
+    let (input, cloud_type) = terminated(opt(nom_cloud_type), not(alphanumeric1)).parse(input)?;
     Ok((
         input,
         Cloud {
@@ -203,7 +316,277 @@ fn nom_cloud(input: &str) -> nom::IResult<&str, Cloud> {
     ))
 }
 
+pub(crate) fn nom_sky_condition(input: &str) -> nom::IResult<&str, SkyCondition> {
+    alt((
+        map(
+            preceded(tag("VV"), nom_cloud_height),
+            SkyCondition::VerticalVisibility,
+        ),
+        value(SkyCondition::NoSignificantCloud, tag("NSC")),
+        value(SkyCondition::NoCloudDetected, tag("NCD")),
+        value(SkyCondition::SkyClear, tag("SKC")),
+        value(SkyCondition::ClearBelow12000Ft, tag("CLR")),
+        map(separated_list1(char(' '), nom_cloud), SkyCondition::Clouds),
+    ))
+    .parse(input)
+}
+
+/// `CAVOK`, or visibility, RVR and cloud groups. Present weather between the
+/// RVR and cloud groups is left unparsed, see [`nom_obscuration_and_weather`].
+pub fn nom_obscuration(input: &str) -> nom::IResult<&str, Obscuration> {
+    map(nom_obscuration_around(success(())), |(obscuration, ())| {
+        obscuration
+    })
+    .parse(input)
+}
+
+/// Present weather is reported between the RVR and cloud groups, so it is
+/// parsed together with the obscuration.
+pub(crate) fn nom_obscuration_and_weather(
+    input: &str,
+) -> nom::IResult<&str, (Obscuration, Vec<PresentWeather>)> {
+    nom_obscuration_around(many0(preceded(char(' '), nom_present_weather))).parse(input)
+}
+
+/// `CAVOK`, or visibility and RVR groups followed by `between` and the sky
+/// condition.
+fn nom_obscuration_around<'a, O: Default>(
+    between: impl Parser<&'a str, Output = O, Error = nom::error::Error<&'a str>>,
+) -> impl Parser<&'a str, Output = (Obscuration, O), Error = nom::error::Error<&'a str>> {
+    alt((
+        map(tag("CAVOK"), |_| (Obscuration::Cavok, O::default())),
+        map(
+            (
+                nom_visibility,
+                many0(preceded(char(' '), nom_rvr)),
+                between,
+                opt(preceded(char(' '), nom_sky_condition)),
+            ),
+            |(visibility, rvr, between, clouds)| {
+                (
+                    Obscuration::Described(DescribedObscuration {
+                        visibility,
+                        rvr,
+                        clouds: clouds.unwrap_or(SkyCondition::Clouds(Vec::new())),
+                    }),
+                    between,
+                )
+            },
+        ),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::weather::{WeatherDescriptor, WeatherIntensity, WeatherPhenomenon};
+
+    #[test]
+    fn test_no_cloud_detected() {
+        assert_eq!(
+            nom_sky_condition("NCD 15/10"),
+            Ok((" 15/10", SkyCondition::NoCloudDetected))
+        );
+    }
+
+    #[test]
+    fn test_vertical_visibility() {
+        assert_eq!(
+            nom_sky_condition("VV002"),
+            Ok((
+                "",
+                SkyCondition::VerticalVisibility(OptionalData::Data(CloudHeight { height: 2 }))
+            ))
+        );
+        assert_eq!(
+            nom_sky_condition("VV///"),
+            Ok((
+                "",
+                SkyCondition::VerticalVisibility(OptionalData::Undefined)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_visibility_ends_at_group_boundary() {
+        assert!(nom_visibility("540104").is_err());
+        assert!(nom_visibility("9999NDVX").is_err());
+    }
+
+    #[test]
+    fn test_no_directional_variation() {
+        let (rest, visibility) = nom_visibility("9999NDV NCD").unwrap();
+        assert_eq!(rest, " NCD");
+        assert_eq!(
+            visibility,
+            Visibility::Meters(MetersVisibility {
+                prevailing: OptionalData::Data(9999),
+                no_directional_variation: true,
+                minimum: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_minimum_visibility() {
+        let (rest, visibility) = nom_visibility("4000 1500SW R24/1200U").unwrap();
+        assert_eq!(rest, " R24/1200U");
+        assert_eq!(
+            visibility,
+            Visibility::Meters(MetersVisibility {
+                prevailing: OptionalData::Data(4000),
+                no_directional_variation: false,
+                minimum: Some(DirectionalVisibility {
+                    meters: 1500,
+                    direction: CompassOctant::SouthWest,
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn test_rvr_tendency() {
+        assert_eq!(
+            nom_rvr("R24/1200U"),
+            Ok((
+                "",
+                Rvr {
+                    runway: "24".to_string(),
+                    value: RvrValue {
+                        value: OptionalData::Data(1200),
+                        distance_modifier: None,
+                    },
+                    variable_max: None,
+                    unit: RvrUnit::Meters,
+                    comment: Some(Trend::Increasing),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rvr_variable() {
+        let (_, rvr) = nom_rvr("R24/M0050VP2000N").unwrap();
+        assert_eq!(
+            rvr.value,
+            RvrValue {
+                value: OptionalData::Data(50),
+                distance_modifier: Some(DistanceModifier::LessThan),
+            }
+        );
+        assert_eq!(
+            rvr.variable_max,
+            Some(RvrValue {
+                value: OptionalData::Data(2000),
+                distance_modifier: Some(DistanceModifier::GreaterThan),
+            })
+        );
+        assert_eq!(rvr.comment, Some(Trend::NoDistinctChange));
+    }
+
+    #[test]
+    fn test_rvr_feet() {
+        let (rest, rvr) = nom_rvr("R28L/2400V4000FT 1/2SM").unwrap();
+        assert_eq!(rest, " 1/2SM");
+        assert_eq!(rvr.runway, "28L");
+        assert_eq!(rvr.unit, RvrUnit::Feet);
+        assert_eq!(rvr.value.value, OptionalData::Data(2400));
+        assert_eq!(
+            rvr.variable_max.map(|max| max.value),
+            Some(OptionalData::Data(4000))
+        );
+        assert_eq!(rvr.comment, None);
+    }
+
+    #[test]
+    fn test_cloud_types() {
+        let (_, cloud) = nom_cloud("SCT015TCU").unwrap();
+        assert_eq!(
+            cloud.cloud_type,
+            Some(OptionalData::Data(CloudType::ToweringCumulus))
+        );
+        let (_, cloud) = nom_cloud("BKN012CB").unwrap();
+        assert_eq!(
+            cloud.cloud_type,
+            Some(OptionalData::Data(CloudType::Cumulonimbus))
+        );
+        let (_, cloud) = nom_cloud("OVC018///").unwrap();
+        assert_eq!(cloud.cloud_type, Some(OptionalData::Undefined));
+        let (_, cloud) = nom_cloud("FEW029").unwrap();
+        assert_eq!(cloud.cloud_type, None);
+    }
+
+    #[test]
+    fn test_invalid_cloud_type() {
+        assert!(nom_cloud("SCT015CU").is_err());
+    }
+
+    #[test]
+    fn test_obscuration_without_weather() {
+        let (rest, obscuration) = nom_obscuration("8000 R24/1200U BKN019 12/10").unwrap();
+        assert_eq!(rest, " 12/10");
+        let Obscuration::Described(described) = obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(described.rvr.len(), 1);
+        assert_eq!(described.clouds.layers().len(), 1);
+        assert_eq!(
+            nom_obscuration("8000 -SHRA BKN019"),
+            Ok((
+                " -SHRA BKN019",
+                Obscuration::Described(DescribedObscuration {
+                    visibility: Visibility::Meters(MetersVisibility::new(OptionalData::Data(8000))),
+                    rvr: vec![],
+                    clouds: SkyCondition::Clouds(vec![]),
+                })
+            ))
+        );
+    }
+
+    #[test]
+    fn test_weather_between_visibility_and_clouds() {
+        let (rest, (obscuration, weather)) =
+            nom_obscuration_and_weather("8000 -SHRA VCFG BKN019 12/10").unwrap();
+        assert_eq!(rest, " 12/10");
+        assert_eq!(
+            weather,
+            vec![
+                PresentWeather {
+                    intensity: WeatherIntensity::Light,
+                    descriptor: Some(WeatherDescriptor::Showers),
+                    phenomena: vec![WeatherPhenomenon::Rain],
+                },
+                PresentWeather {
+                    intensity: WeatherIntensity::Vicinity,
+                    descriptor: None,
+                    phenomena: vec![WeatherPhenomenon::Fog],
+                },
+            ]
+        );
+        let Obscuration::Described(described) = obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(
+            described.visibility,
+            Visibility::Meters(MetersVisibility::new(OptionalData::Data(8000)))
+        );
+        assert_eq!(described.clouds.layers().len(), 1);
+    }
+
+    #[test]
+    fn test_cavok_has_no_weather() {
+        assert_eq!(
+            nom_obscuration_and_weather("CAVOK 10/03"),
+            Ok((" 10/03", (Obscuration::Cavok, vec![])))
+        );
+    }
+
+    #[test]
+    fn test_no_clouds_reported() {
+        let (_, (obscuration, _)) = nom_obscuration_and_weather("0800 FG 12/12").unwrap();
+        let Obscuration::Described(described) = obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(described.clouds, SkyCondition::Clouds(vec![]));
+    }
 }
@@ -1,10 +1,11 @@
 use std::fmt::Display;
 
 use nom::{
-    AsChar, Input, Parser,
+    AsChar, IResult, Input, Parser,
     branch::{Choice, alt},
-    character::complete::char,
-    combinator::{map, value},
+    bytes::complete::take,
+    character::complete::{char, u32},
+    combinator::{all_consuming, map, map_parser, value},
     error::ParseError,
     multi::count,
 };
@@ -29,6 +30,7 @@ impl<T, const N: usize> OptionalData<T, N> {
 
 impl<T: Clone, const N: usize> OptionalData<T, N> {
     // Ugly type signature. Should be able to return impl Parser....
+    #[allow(clippy::type_complexity)]
     pub fn optional_field<P, I, E: ParseError<I>>(
         p: P,
     ) -> Choice<(
@@ -47,6 +49,11 @@ impl<T: Clone, const N: usize> OptionalData<T, N> {
     }
 }
 
+/// Exactly `digits` decimal digits.
+pub(crate) fn nom_digits(digits: usize) -> impl FnMut(&str) -> IResult<&str, u32> {
+    move |input| map_parser(take(digits), all_consuming(u32)).parse(input)
+}
+
 impl<T, const N: usize> Display for OptionalData<T, N>
 where
     T: Display,
@@ -59,7 +66,7 @@ where
     }
 }
 
-impl <const N: usize, T> From<OptionalData<T, N>> for Option<T> {
+impl<const N: usize, T> From<OptionalData<T, N>> for Option<T> {
     fn from(value: OptionalData<T, N>) -> Self {
         match value {
             OptionalData::Undefined => None,
@@ -68,7 +75,6 @@ impl <const N: usize, T> From<OptionalData<T, N>> for Option<T> {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
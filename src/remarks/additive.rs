@@ -0,0 +1,293 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, one_of},
+    combinator::{map, opt, recognize, value},
+    sequence::preceded,
+};
+
+use crate::{
+    optional_data::{OptionalData, nom_digits},
+    remarks::north_american::nom_signed_tenths,
+    runway::nom_runway_designator,
+    units::compass::nom_compass_octant,
+};
+
+const MILLIMETERS_PER_INCH: f64 = 25.4;
+
+/// Additive data and maintenance indicators appended by US automated stations.
+/// Temperatures are in tenths of a degree Celsius.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AdditiveData {
+    /// `Prrrr`: precipitation during the last hour.
+    pub hourly_precipitation: Option<OptionalData<Precipitation, 4>>,
+    /// `6RRRR`: precipitation during the last 3 or 6 hours, depending on the
+    /// report time.
+    pub period_precipitation: Option<OptionalData<Precipitation, 4>>,
+    /// `7RRRR`: precipitation during the last 24 hours.
+    pub daily_precipitation: Option<OptionalData<Precipitation, 4>>,
+    /// `1snTTT`: maximum temperature during the last 6 hours.
+    pub six_hour_maximum_temperature: Option<i32>,
+    /// `2snTTT`: minimum temperature during the last 6 hours.
+    pub six_hour_minimum_temperature: Option<i32>,
+    /// `4snTTTsnTTT`: maximum and minimum temperature during the last 24 hours.
+    pub daily_temperature: Option<DailyTemperature>,
+    /// `5appp`
+    pub pressure_tendency: Option<PressureTendency>,
+    /// `4/sss`: snow depth on the ground in inches.
+    pub snow_depth: Option<u32>,
+    pub sensor_status: Vec<SensorStatus>,
+    /// `$`: the station needs maintenance.
+    pub maintenance_needed: bool,
+}
+
+/// Precipitation amount in hundredths of an inch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Precipitation(pub u32);
+
+impl Precipitation {
+    pub fn inches(&self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+
+    pub fn millimeters(&self) -> f64 {
+        self.inches() * MILLIMETERS_PER_INCH
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyTemperature {
+    pub maximum: i32,
+    pub minimum: i32,
+}
+
+/// Pressure change over the last three hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressureTendency {
+    /// Characteristic of the change, WMO code table 0200. `0`-`3` mean the
+    /// pressure is higher than three hours ago, `5`-`8` lower.
+    pub characteristic: u32,
+    /// Amount of change in tenths of a hectopascal.
+    pub change: u32,
+}
+
+impl PressureTendency {
+    /// Change in tenths of a hectopascal, negative if the pressure fell.
+    pub fn signed_change(&self) -> i32 {
+        let change = self.change as i32;
+        if self.characteristic >= 5 {
+            -change
+        } else {
+            change
+        }
+    }
+}
+
+/// Sensors reported as not operational.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SensorStatus {
+    /// `RVRNO`
+    RunwayVisualRange,
+    /// `PWINO`: present weather identifier.
+    PresentWeather,
+    /// `PNO`: precipitation amount.
+    Precipitation,
+    /// `FZRANO`: freezing rain sensor.
+    FreezingRain,
+    /// `TSNO`: lightning detection.
+    Lightning,
+    /// `SLPNO`: sea-level pressure.
+    SeaLevelPressure,
+    /// `VISNO`, optionally with the location of the secondary sensor.
+    Visibility(Option<String>),
+    /// `CHINO`, optionally with the location of the secondary sensor.
+    CloudHeight(Option<String>),
+}
+
+#[derive(Clone)]
+pub(crate) enum AdditiveGroup {
+    HourlyPrecipitation(OptionalData<Precipitation, 4>),
+    PeriodPrecipitation(OptionalData<Precipitation, 4>),
+    DailyPrecipitation(OptionalData<Precipitation, 4>),
+    SixHourMaximumTemperature(i32),
+    SixHourMinimumTemperature(i32),
+    DailyTemperature(DailyTemperature),
+    PressureTendency(PressureTendency),
+    SnowDepth(u32),
+    SensorStatus(SensorStatus),
+    MaintenanceNeeded,
+}
+
+impl AdditiveData {
+    pub(crate) fn push(&mut self, group: AdditiveGroup) {
+        match group {
+            AdditiveGroup::HourlyPrecipitation(amount) => self.hourly_precipitation = Some(amount),
+            AdditiveGroup::PeriodPrecipitation(amount) => self.period_precipitation = Some(amount),
+            AdditiveGroup::DailyPrecipitation(amount) => self.daily_precipitation = Some(amount),
+            AdditiveGroup::SixHourMaximumTemperature(temperature) => {
+                self.six_hour_maximum_temperature = Some(temperature)
+            }
+            AdditiveGroup::SixHourMinimumTemperature(temperature) => {
+                self.six_hour_minimum_temperature = Some(temperature)
+            }
+            AdditiveGroup::DailyTemperature(temperature) => {
+                self.daily_temperature = Some(temperature)
+            }
+            AdditiveGroup::PressureTendency(tendency) => self.pressure_tendency = Some(tendency),
+            AdditiveGroup::SnowDepth(depth) => self.snow_depth = Some(depth),
+            AdditiveGroup::SensorStatus(status) => self.sensor_status.push(status),
+            AdditiveGroup::MaintenanceNeeded => self.maintenance_needed = true,
+        }
+    }
+}
+
+fn nom_precipitation(input: &str) -> IResult<&str, OptionalData<Precipitation, 4>> {
+    OptionalData::optional_field(map(nom_digits(4), Precipitation)).parse(input)
+}
+
+fn nom_pressure_tendency(input: &str) -> IResult<&str, PressureTendency> {
+    preceded(char('5'), (one_of("012345678"), nom_digits(3)))
+        .map(|(characteristic, change)| PressureTendency {
+            characteristic: characteristic.to_digit(10).unwrap(),
+            change,
+        })
+        .parse(input)
+}
+
+/// Location of a secondary visibility or ceiling sensor, e.g. `RWY06` or `NE`.
+fn nom_sensor_location(input: &str) -> IResult<&str, String> {
+    map(
+        recognize(alt((
+            preceded(tag("RWY"), map(nom_runway_designator, |_| ())),
+            map(nom_compass_octant, |_| ()),
+        ))),
+        str::to_string,
+    )
+    .parse(input)
+}
+
+fn nom_sensor_status(input: &str) -> IResult<&str, SensorStatus> {
+    alt((
+        value(SensorStatus::RunwayVisualRange, tag("RVRNO")),
+        value(SensorStatus::PresentWeather, tag("PWINO")),
+        value(SensorStatus::Precipitation, tag("PNO")),
+        value(SensorStatus::FreezingRain, tag("FZRANO")),
+        value(SensorStatus::Lightning, tag("TSNO")),
+        value(SensorStatus::SeaLevelPressure, tag("SLPNO")),
+        map(
+            preceded(tag("VISNO"), opt(preceded(char(' '), nom_sensor_location))),
+            SensorStatus::Visibility,
+        ),
+        map(
+            preceded(tag("CHINO"), opt(preceded(char(' '), nom_sensor_location))),
+            SensorStatus::CloudHeight,
+        ),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_additive_group(input: &str) -> IResult<&str, AdditiveGroup> {
+    alt((
+        map(
+            preceded(char('P'), nom_precipitation),
+            AdditiveGroup::HourlyPrecipitation,
+        ),
+        map(
+            preceded(char('6'), nom_precipitation),
+            AdditiveGroup::PeriodPrecipitation,
+        ),
+        map(
+            preceded(char('7'), nom_precipitation),
+            AdditiveGroup::DailyPrecipitation,
+        ),
+        map(
+            preceded(char('1'), nom_signed_tenths),
+            AdditiveGroup::SixHourMaximumTemperature,
+        ),
+        map(
+            preceded(char('2'), nom_signed_tenths),
+            AdditiveGroup::SixHourMinimumTemperature,
+        ),
+        map(preceded(tag("4/"), nom_digits(3)), AdditiveGroup::SnowDepth),
+        map(
+            preceded(char('4'), (nom_signed_tenths, nom_signed_tenths)),
+            |(maximum, minimum)| {
+                AdditiveGroup::DailyTemperature(DailyTemperature { maximum, minimum })
+            },
+        ),
+        map(nom_pressure_tendency, AdditiveGroup::PressureTendency),
+        map(nom_sensor_status, AdditiveGroup::SensorStatus),
+        value(AdditiveGroup::MaintenanceNeeded, char('$')),
+    ))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_all(groups: &str) -> AdditiveData {
+        let mut data = AdditiveData::default();
+        for group in groups.split(' ') {
+            let (rest, group) = nom_additive_group(group).unwrap();
+            assert_eq!(rest, "");
+            data.push(group);
+        }
+        data
+    }
+
+    #[test]
+    fn test_precipitation() {
+        let data = parse_all("P0012 60034 7////");
+        assert_eq!(
+            data.hourly_precipitation,
+            Some(OptionalData::Data(Precipitation(12)))
+        );
+        assert_eq!(
+            data.period_precipitation,
+            Some(OptionalData::Data(Precipitation(34)))
+        );
+        assert_eq!(data.daily_precipitation, Some(OptionalData::Undefined));
+        assert_eq!(Precipitation(100).millimeters(), 25.4);
+    }
+
+    #[test]
+    fn test_temperatures() {
+        let data = parse_all("10142 21001 401001015");
+        assert_eq!(data.six_hour_maximum_temperature, Some(142));
+        assert_eq!(data.six_hour_minimum_temperature, Some(-1));
+        assert_eq!(
+            data.daily_temperature,
+            Some(DailyTemperature {
+                maximum: 100,
+                minimum: -15
+            })
+        );
+    }
+
+    #[test]
+    fn test_pressure_tendency_and_snow_depth() {
+        let data = parse_all("56012 4/021");
+        let tendency = data.pressure_tendency.unwrap();
+        assert_eq!(tendency.characteristic, 6);
+        assert_eq!(tendency.signed_change(), -12);
+        assert_eq!(data.snow_depth, Some(21));
+    }
+
+    #[test]
+    fn test_sensor_status() {
+        let mut data = parse_all("RVRNO PWINO $");
+        let (_, group) = nom_additive_group("VISNO RWY06").unwrap();
+        data.push(group);
+        assert_eq!(
+            data.sensor_status,
+            vec![
+                SensorStatus::RunwayVisualRange,
+                SensorStatus::PresentWeather,
+                SensorStatus::Visibility(Some("RWY06".to_string())),
+            ]
+        );
+        assert!(data.maintenance_needed);
+    }
+}
@@ -0,0 +1,150 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::one_of,
+    combinator::{map, value},
+    multi::many1,
+};
+
+use crate::{
+    obscuration::{Cloud, SkyCondition},
+    weather::{WeatherPhenomenon, nom_weather_phenomenon},
+};
+
+/// Genus and opacity of one layer, from Canadian remarks like `SC4AC2CI1`.
+/// The layers are listed in the same order as in the sky condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CloudOpacity {
+    pub genus: CloudGenus,
+    /// Opacity in oktas, `0`-`8`.
+    pub oktas: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudGenus {
+    Cirrus,
+    Cirrocumulus,
+    Cirrostratus,
+    Altocumulus,
+    /// `ACC`
+    AltocumulusCastellanus,
+    Altostratus,
+    Nimbostratus,
+    Stratocumulus,
+    Stratus,
+    /// `SF`
+    StratusFractus,
+    Cumulus,
+    /// `CF`
+    CumulusFractus,
+    /// `TCU`
+    ToweringCumulus,
+    Cumulonimbus,
+    /// A layer of obscuring phenomena such as `FG3`.
+    Obscuring(WeatherPhenomenon),
+}
+
+/// Pairs each cloud layer with its reported genus and opacity. Layers without
+/// a matching remark are skipped.
+pub fn layers_with_opacity<'a>(
+    sky: &'a SkyCondition,
+    opacity: &'a [CloudOpacity],
+) -> impl Iterator<Item = (&'a Cloud, &'a CloudOpacity)> {
+    sky.layers().iter().zip(opacity)
+}
+
+fn nom_cloud_genus(input: &str) -> IResult<&str, CloudGenus> {
+    alt((
+        value(CloudGenus::AltocumulusCastellanus, tag("ACC")),
+        value(CloudGenus::ToweringCumulus, tag("TCU")),
+        value(CloudGenus::Cirrus, tag("CI")),
+        value(CloudGenus::Cirrocumulus, tag("CC")),
+        value(CloudGenus::Cirrostratus, tag("CS")),
+        value(CloudGenus::Altocumulus, tag("AC")),
+        value(CloudGenus::Altostratus, tag("AS")),
+        value(CloudGenus::Nimbostratus, tag("NS")),
+        value(CloudGenus::Stratocumulus, tag("SC")),
+        value(CloudGenus::Stratus, tag("ST")),
+        value(CloudGenus::StratusFractus, tag("SF")),
+        value(CloudGenus::Cumulus, tag("CU")),
+        value(CloudGenus::CumulusFractus, tag("CF")),
+        value(CloudGenus::Cumulonimbus, tag("CB")),
+        map(nom_weather_phenomenon, CloudGenus::Obscuring),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_cloud_opacity(input: &str) -> IResult<&str, Vec<CloudOpacity>> {
+    many1(
+        (nom_cloud_genus, one_of("012345678")).map(|(genus, oktas)| CloudOpacity {
+            genus,
+            oktas: oktas.to_digit(10).unwrap(),
+        }),
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        metar::nom_parse_metar, obscuration::Obscuration, optional_data::OptionalData,
+        units::altitudes::CloudHeight,
+    };
+
+    #[test]
+    fn test_cloud_opacity() {
+        let (_, layers) = nom_cloud_opacity("SC4AC2CI1").unwrap();
+        assert_eq!(
+            layers,
+            vec![
+                CloudOpacity {
+                    genus: CloudGenus::Stratocumulus,
+                    oktas: 4
+                },
+                CloudOpacity {
+                    genus: CloudGenus::Altocumulus,
+                    oktas: 2
+                },
+                CloudOpacity {
+                    genus: CloudGenus::Cirrus,
+                    oktas: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_obscuring_layer() {
+        let (_, layers) = nom_cloud_opacity("FG3SC3").unwrap();
+        assert_eq!(
+            layers[0].genus,
+            CloudGenus::Obscuring(WeatherPhenomenon::Fog)
+        );
+        assert!(nom_cloud_opacity("SC9").is_err());
+    }
+
+    #[test]
+    fn test_layers_with_opacity() {
+        let (_, metar) = nom_parse_metar(
+            "CYUL 291800Z 24010KT 15SM FEW030 SCT080 BKN250 22/12 A2992 RMK CU2AC3CI1",
+        )
+        .unwrap();
+        let Obscuration::Described(obscuration) = &metar.obscuration else {
+            panic!("expected described obscuration");
+        };
+        let remarks = metar.remarks.as_ref().unwrap();
+        let layers: Vec<_> = layers_with_opacity(&obscuration.clouds, &remarks.cloud_opacity)
+            .map(|(cloud, opacity)| (cloud.height.clone(), opacity.genus))
+            .collect();
+        assert_eq!(layers.len(), 3);
+        assert_eq!(
+            layers[1],
+            (
+                OptionalData::Data(CloudHeight { height: 80 }),
+                CloudGenus::Altocumulus
+            )
+        );
+    }
+}
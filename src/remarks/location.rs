@@ -0,0 +1,162 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char},
+    combinator::{map, not, opt, value, verify},
+    multi::separated_list1,
+    sequence::{preceded, terminated},
+};
+
+use crate::units::compass::{CompassOctant, nom_compass_octant};
+
+/// Where a phenomenon is observed relative to the station and where it is
+/// heading, e.g. `DSNT NE-SE` or `OHD MOV E`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PhenomenonLocation {
+    pub distance: Option<LocationDistance>,
+    /// Empty if no direction is given.
+    pub directions: Vec<DirectionRange>,
+    /// `ALQDS`: reported in all quadrants.
+    pub all_quadrants: bool,
+    pub movement: Option<Movement>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocationDistance {
+    /// `OHD`
+    Overhead,
+    /// `VC`: 5 to 10 statute miles from the station.
+    Vicinity,
+    /// `DSNT`: more than 10 statute miles from the station.
+    Distant,
+}
+
+/// A single octant (`from == to`) or a clockwise range such as `NE-SE` or
+/// `N THRU E`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectionRange {
+    pub from: CompassOctant,
+    pub to: CompassOctant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    /// `MOV E`: moving towards the given octant.
+    Towards(CompassOctant),
+    /// `STNRY`
+    Stationary,
+}
+
+fn nom_location_distance(input: &str) -> IResult<&str, LocationDistance> {
+    alt((
+        value(LocationDistance::Overhead, tag("OHD")),
+        value(LocationDistance::Vicinity, tag("VC")),
+        value(LocationDistance::Distant, tag("DSNT")),
+    ))
+    .parse(input)
+}
+
+fn nom_octant(input: &str) -> IResult<&str, CompassOctant> {
+    terminated(nom_compass_octant, not(alphanumeric1)).parse(input)
+}
+
+fn nom_direction_range(input: &str) -> IResult<&str, DirectionRange> {
+    (
+        nom_octant,
+        opt(preceded(alt((tag("-"), tag(" THRU "))), nom_octant)),
+    )
+        .map(|(from, to)| DirectionRange {
+            from,
+            to: to.unwrap_or(from),
+        })
+        .parse(input)
+}
+
+fn nom_directions(input: &str) -> IResult<&str, Vec<DirectionRange>> {
+    separated_list1(tag(" AND "), nom_direction_range).parse(input)
+}
+
+fn nom_movement(input: &str) -> IResult<&str, Movement> {
+    alt((
+        map(
+            preceded(alt((tag("MOVD "), tag("MOV "))), nom_octant),
+            Movement::Towards,
+        ),
+        value(Movement::Stationary, tag("STNRY")),
+    ))
+    .parse(input)
+}
+
+/// Parses the location groups following a phenomenon, each preceded by a
+/// space. Fails if none are present.
+pub(crate) fn nom_phenomenon_location(input: &str) -> IResult<&str, PhenomenonLocation> {
+    verify(
+        (
+            opt(preceded(
+                char(' '),
+                terminated(nom_location_distance, not(alphanumeric1)),
+            )),
+            opt(preceded(char(' '), tag("ALQDS"))),
+            opt(preceded(char(' '), nom_directions)),
+            opt(preceded(char(' '), nom_movement)),
+        )
+            .map(
+                |(distance, all_quadrants, directions, movement)| PhenomenonLocation {
+                    distance,
+                    directions: directions.unwrap_or_default(),
+                    all_quadrants: all_quadrants.is_some(),
+                    movement,
+                },
+            ),
+        |location| *location != PhenomenonLocation::default(),
+    )
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distant_range() {
+        let (rest, location) = nom_phenomenon_location(" DSNT NE-SE SLP132").unwrap();
+        assert_eq!(rest, " SLP132");
+        assert_eq!(
+            location,
+            PhenomenonLocation {
+                distance: Some(LocationDistance::Distant),
+                directions: vec![DirectionRange {
+                    from: CompassOctant::NorthEast,
+                    to: CompassOctant::SouthEast,
+                }],
+                all_quadrants: false,
+                movement: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_overhead_moving() {
+        let (_, location) = nom_phenomenon_location(" OHD MOV E").unwrap();
+        assert_eq!(location.distance, Some(LocationDistance::Overhead));
+        assert!(location.directions.is_empty());
+        assert_eq!(
+            location.movement,
+            Some(Movement::Towards(CompassOctant::East))
+        );
+    }
+
+    #[test]
+    fn test_multiple_directions() {
+        let (_, location) = nom_phenomenon_location(" VC N THRU E AND SW").unwrap();
+        assert_eq!(location.directions.len(), 2);
+        assert_eq!(location.directions[1].from, CompassOctant::SouthWest);
+        assert_eq!(location.directions[1].to, CompassOctant::SouthWest);
+    }
+
+    #[test]
+    fn test_no_location() {
+        assert!(nom_phenomenon_location(" NOSIG").is_err());
+    }
+}
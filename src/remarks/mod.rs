@@ -0,0 +1,334 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_till},
+    combinator::{eof, map, peek, verify},
+    sequence::terminated,
+};
+
+use crate::{
+    obscuration::StatuteMilesVisibility,
+    remarks::{
+        additive::{AdditiveData, AdditiveGroup, nom_additive_group},
+        canadian::{CloudOpacity, nom_cloud_opacity},
+        north_american::{
+            PeakWind, PreciseTemperature, RapidPressureChange, SeaLevelPressure, StationType,
+            WindShift, nom_peak_wind, nom_precise_temperature, nom_rapid_pressure_change,
+            nom_sea_level_pressure, nom_station_type, nom_surface_visibility, nom_tower_visibility,
+            nom_wind_shift,
+        },
+        phenomena::{
+            SignificantPhenomenon, WeatherEvents, nom_significant_phenomenon, nom_weather_events,
+        },
+        russian::{Qfe, nom_cloud_base, nom_qfe},
+        wind::{RemarkWind, nom_remark_wind},
+    },
+    runway::{RunwayState, nom_runway_state},
+    units::timestamp::{Timestamp, parse_double_digit},
+};
+
+pub mod additive;
+pub mod canadian;
+pub mod location;
+pub mod north_american;
+pub mod phenomena;
+pub mod russian;
+pub mod wind;
+
+/// Decoded remark section. Groups that are not understood are kept in
+/// `unparsed` in the order they appear.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Remarks {
+    pub raw: String,
+    pub winds: Vec<RemarkWind>,
+    pub station_type: Option<StationType>,
+    pub sea_level_pressure: Option<SeaLevelPressure>,
+    pub precise_temperature: Option<PreciseTemperature>,
+    pub peak_wind: Option<PeakWind>,
+    pub wind_shift: Option<WindShift>,
+    pub rapid_pressure_change: Option<RapidPressureChange>,
+    pub tower_visibility: Option<StatuteMilesVisibility>,
+    pub surface_visibility: Option<StatuteMilesVisibility>,
+    pub additive: AdditiveData,
+    pub weather_events: Vec<WeatherEvents>,
+    pub phenomena: Vec<SignificantPhenomenon>,
+    pub qfe: Option<Qfe>,
+    /// `QBB`: cloud base in metres.
+    pub cloud_base: Option<u32>,
+    pub runway_state: Vec<RunwayState>,
+    /// Canadian cloud genus and opacity per layer, see
+    /// [`canadian::layers_with_opacity`].
+    pub cloud_opacity: Vec<CloudOpacity>,
+    pub unparsed: Vec<String>,
+}
+
+enum RemarkGroup {
+    Wind(RemarkWind),
+    StationType(StationType),
+    SeaLevelPressure(SeaLevelPressure),
+    PreciseTemperature(PreciseTemperature),
+    PeakWind(PeakWind),
+    WindShift(WindShift),
+    RapidPressureChange(RapidPressureChange),
+    TowerVisibility(StatuteMilesVisibility),
+    SurfaceVisibility(StatuteMilesVisibility),
+    Additive(AdditiveGroup),
+    WeatherEvents(Vec<WeatherEvents>),
+    Phenomenon(SignificantPhenomenon),
+    Qfe(Qfe),
+    CloudBase(u32),
+    RunwayState(RunwayState),
+    CloudOpacity(Vec<CloudOpacity>),
+}
+
+impl Remarks {
+    fn push(&mut self, group: RemarkGroup) {
+        match group {
+            RemarkGroup::Wind(wind) => self.winds.push(wind),
+            RemarkGroup::StationType(station_type) => self.station_type = Some(station_type),
+            RemarkGroup::SeaLevelPressure(pressure) => self.sea_level_pressure = Some(pressure),
+            RemarkGroup::PreciseTemperature(temperature) => {
+                self.precise_temperature = Some(temperature)
+            }
+            RemarkGroup::PeakWind(peak_wind) => self.peak_wind = Some(peak_wind),
+            RemarkGroup::WindShift(wind_shift) => self.wind_shift = Some(wind_shift),
+            RemarkGroup::RapidPressureChange(change) => self.rapid_pressure_change = Some(change),
+            RemarkGroup::TowerVisibility(visibility) => self.tower_visibility = Some(visibility),
+            RemarkGroup::SurfaceVisibility(visibility) => {
+                self.surface_visibility = Some(visibility)
+            }
+            RemarkGroup::Additive(group) => self.additive.push(group),
+            RemarkGroup::WeatherEvents(events) => self.weather_events.extend(events),
+            RemarkGroup::Phenomenon(phenomenon) => self.phenomena.push(phenomenon),
+            RemarkGroup::Qfe(qfe) => self.qfe = Some(qfe),
+            RemarkGroup::CloudBase(height) => self.cloud_base = Some(height),
+            RemarkGroup::RunwayState(state) => self.runway_state.push(state),
+            RemarkGroup::CloudOpacity(layers) => self.cloud_opacity = layers,
+        }
+    }
+}
+
+/// Time of an event in remarks, `hhmm` or just `mm` within the hour of the
+/// report. Always resolved to the past of the report time.
+pub(crate) fn nom_remark_time<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, Zoned> {
+    verify(
+        alt((
+            map(
+                (parse_double_digit, parse_double_digit),
+                |(hour, minute)| (Some(hour), minute),
+            ),
+            map(parse_double_digit, |minute| (None, minute)),
+        )),
+        |(hour, minute)| {
+            hour.is_none_or(|hour| (0..24).contains(&hour)) && (0..60).contains(minute)
+        },
+    )
+    .map(|(hour, minute)| timestamp.previous_time(hour, minute))
+    .parse(input)
+}
+
+/// Remark groups end at a space or the end of the remarks. This is checked
+/// per alternative so that a group that only matches a prefix of the token,
+/// like `10142` in a runway state `10290195`, lets the next one be tried.
+fn nom_group_end(input: &str) -> IResult<&str, &str> {
+    peek(alt((tag(" "), eof))).parse(input)
+}
+
+fn nom_remark_group<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, RemarkGroup> {
+    alt((
+        terminated(map(nom_remark_wind, RemarkGroup::Wind), nom_group_end),
+        terminated(
+            map(nom_station_type, RemarkGroup::StationType),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_sea_level_pressure, RemarkGroup::SeaLevelPressure),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_precise_temperature, RemarkGroup::PreciseTemperature),
+            nom_group_end,
+        ),
+        terminated(
+            map(|i| nom_peak_wind(i, timestamp), RemarkGroup::PeakWind),
+            nom_group_end,
+        ),
+        terminated(
+            map(|i| nom_wind_shift(i, timestamp), RemarkGroup::WindShift),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_rapid_pressure_change, RemarkGroup::RapidPressureChange),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_tower_visibility, RemarkGroup::TowerVisibility),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_surface_visibility, RemarkGroup::SurfaceVisibility),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_additive_group, RemarkGroup::Additive),
+            nom_group_end,
+        ),
+        terminated(
+            map(
+                |i| nom_weather_events(i, timestamp),
+                RemarkGroup::WeatherEvents,
+            ),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_significant_phenomenon, RemarkGroup::Phenomenon),
+            nom_group_end,
+        ),
+        terminated(map(nom_qfe, RemarkGroup::Qfe), nom_group_end),
+        terminated(map(nom_cloud_base, RemarkGroup::CloudBase), nom_group_end),
+        terminated(
+            map(nom_runway_state, RemarkGroup::RunwayState),
+            nom_group_end,
+        ),
+        terminated(
+            map(nom_cloud_opacity, RemarkGroup::CloudOpacity),
+            nom_group_end,
+        ),
+    ))
+    .parse(input)
+}
+
+fn nom_unparsed_group(input: &str) -> IResult<&str, &str> {
+    take_till(|c| c == ' ').parse(input)
+}
+
+/// Decodes the text following `RMK`. This never fails, anything that is not
+/// recognised ends up in [`Remarks::unparsed`]. Times are resolved against the
+/// report timestamp.
+pub(crate) fn parse_remarks(input: &str, timestamp: &Timestamp) -> Remarks {
+    let mut remarks = Remarks {
+        raw: input.to_string(),
+        ..Default::default()
+    };
+    let mut rest = input.trim_start();
+    while !rest.is_empty() {
+        if let Ok((next, group)) = nom_remark_group(rest, timestamp) {
+            remarks.push(group);
+            rest = next;
+        } else {
+            let (next, unparsed) = nom_unparsed_group(rest).unwrap_or(("", rest));
+            remarks.unparsed.push(unparsed.to_string());
+            rest = next;
+        }
+        rest = rest.trim_start();
+    }
+    remarks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{remarks::wind::RemarkWindLocation, units::timestamp::test_time};
+
+    #[test]
+    fn test_winds_and_unparsed() {
+        let remarks = parse_remarks(
+            "WIND RWY 24 VRB03KT WIND 2090FT 28012KT BAR FOO",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        );
+        assert_eq!(
+            remarks
+                .winds
+                .iter()
+                .map(|w| w.location.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                RemarkWindLocation::Runway("24".to_string()),
+                RemarkWindLocation::Height(2090)
+            ]
+        );
+        assert_eq!(remarks.unparsed, vec!["BAR", "FOO"]);
+    }
+
+    #[test]
+    fn test_unparsed_keeps_order() {
+        let remarks = parse_remarks(
+            "WIND 1200FT",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        );
+        assert!(remarks.winds.is_empty());
+        assert_eq!(remarks.unparsed, vec!["WIND", "1200FT"]);
+    }
+
+    #[test]
+    fn test_north_american_remarks() {
+        let remarks = parse_remarks(
+            "AO2 PK WND 28045/1955 WSHFT 1930 FROPA TWR VIS 1 1/2 PRESRR SLP132 T01280106",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        );
+        assert_eq!(
+            remarks.station_type,
+            Some(StationType::WithPrecipitationDiscriminator)
+        );
+        assert_eq!(remarks.peak_wind.map(|p| p.speed), Some(45));
+        assert!(remarks.wind_shift.unwrap().frontal_passage);
+        assert_eq!(remarks.tower_visibility.unwrap().whole, Some(1));
+        assert_eq!(
+            remarks.rapid_pressure_change,
+            Some(RapidPressureChange::RisingRapidly)
+        );
+        assert_eq!(remarks.sea_level_pressure, Some(SeaLevelPressure(10132)));
+        assert_eq!(remarks.precise_temperature.unwrap().temperature, 128);
+        assert!(remarks.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_additive_remarks() {
+        let remarks = parse_remarks(
+            "AO2 SLP132 P0003 60009 T01280106 10139 20111 53012 PNO $",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        );
+        assert_eq!(remarks.additive.six_hour_maximum_temperature, Some(139));
+        assert_eq!(
+            remarks.additive.pressure_tendency.unwrap().signed_change(),
+            12
+        );
+        assert!(remarks.additive.maintenance_needed);
+        assert!(remarks.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_events_and_phenomena() {
+        let remarks = parse_remarks(
+            "AO2 LTG DSNT NE-SE RAB15E30SNB30 TSNO CB OHD MOV E VIRGA SW FC W",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        );
+        assert_eq!(remarks.weather_events.len(), 2);
+        assert_eq!(remarks.phenomena.len(), 4);
+        assert_eq!(remarks.additive.sensor_status.len(), 1);
+        assert!(remarks.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_russian_remarks() {
+        let remarks = parse_remarks(
+            "QFE747/0996 QBB180 R24/290150",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        );
+        assert_eq!(remarks.qfe.unwrap().hectopascals, Some(996));
+        assert_eq!(remarks.cloud_base, Some(180));
+        assert_eq!(remarks.runway_state.len(), 1);
+        assert!(remarks.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_group_end_per_alternative() {
+        let remarks = parse_remarks("10290195", &Timestamp::new(test_time(2025, 6, 29, 20, 53)));
+        assert!(remarks.additive.six_hour_maximum_temperature.is_none());
+        assert_eq!(remarks.runway_state.len(), 1);
+    }
+}
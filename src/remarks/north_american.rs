@@ -0,0 +1,267 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take, take_while_m_n},
+    character::complete::{char, one_of, u32},
+    combinator::{all_consuming, map, map_parser, opt, value},
+    sequence::preceded,
+};
+
+use crate::{
+    obscuration::{StatuteMilesVisibility, nom_distance_modifier, nom_fraction},
+    remarks::nom_remark_time,
+    units::{
+        timestamp::Timestamp,
+        track::{Track, nom_track},
+    },
+};
+
+/// Type of automated station, `AO1` or `AO2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StationType {
+    /// `AO1`: no precipitation discriminator.
+    WithoutPrecipitationDiscriminator,
+    /// `AO2`: can tell rain from snow.
+    WithPrecipitationDiscriminator,
+}
+
+/// `SLPppp`: sea-level pressure in tenths of a hectopascal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeaLevelPressure(pub u32);
+
+impl SeaLevelPressure {
+    pub fn hectopascals(&self) -> f64 {
+        f64::from(self.0) / 10.0
+    }
+}
+
+/// `TsTTTsTTT`: temperature and dew point in tenths of a degree Celsius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreciseTemperature {
+    pub temperature: i32,
+    pub dew_point: Option<i32>,
+}
+
+impl PreciseTemperature {
+    pub fn celsius(&self) -> f64 {
+        f64::from(self.temperature) / 10.0
+    }
+
+    pub fn dew_point_celsius(&self) -> Option<f64> {
+        self.dew_point.map(|dew_point| f64::from(dew_point) / 10.0)
+    }
+}
+
+/// `PK WND dddff(f)/(hh)mm`: highest instantaneous wind speed since the last
+/// routine report, in knots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeakWind {
+    pub direction: Track,
+    pub speed: u32,
+    pub time: Zoned,
+}
+
+/// `WSHFT (hh)mm`, with `FROPA` if the shift is due to a frontal passage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindShift {
+    pub time: Zoned,
+    pub frontal_passage: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RapidPressureChange {
+    /// `PRESRR`
+    RisingRapidly,
+    /// `PRESFR`
+    FallingRapidly,
+}
+
+pub(crate) fn nom_station_type(input: &str) -> IResult<&str, StationType> {
+    alt((
+        value(StationType::WithoutPrecipitationDiscriminator, tag("AO1")),
+        value(StationType::WithPrecipitationDiscriminator, tag("AO2")),
+    ))
+    .parse(input)
+}
+
+/// Only the last three digits are reported, values from `500` and up are
+/// below 1000 hPa.
+pub(crate) fn nom_sea_level_pressure(input: &str) -> IResult<&str, SeaLevelPressure> {
+    preceded(tag("SLP"), map_parser(take(3usize), all_consuming(u32)))
+        .map(|pressure| {
+            if pressure >= 500 {
+                SeaLevelPressure(9000 + pressure)
+            } else {
+                SeaLevelPressure(10000 + pressure)
+            }
+        })
+        .parse(input)
+}
+
+/// A sign digit (`1` for negative) followed by three digits in tenths.
+pub(crate) fn nom_signed_tenths(input: &str) -> IResult<&str, i32> {
+    (one_of("01"), map_parser(take(3usize), all_consuming(u32)))
+        .map(|(sign, value)| {
+            let value = value as i32;
+            if sign == '1' { -value } else { value }
+        })
+        .parse(input)
+}
+
+pub(crate) fn nom_precise_temperature(input: &str) -> IResult<&str, PreciseTemperature> {
+    preceded(char('T'), (nom_signed_tenths, opt(nom_signed_tenths)))
+        .map(|(temperature, dew_point)| PreciseTemperature {
+            temperature,
+            dew_point,
+        })
+        .parse(input)
+}
+
+pub(crate) fn nom_peak_wind<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, PeakWind> {
+    preceded(
+        tag("PK WND "),
+        (
+            nom_track,
+            map_parser(
+                take_while_m_n(2, 3, |c: char| c.is_ascii_digit()),
+                all_consuming(u32),
+            ),
+            preceded(char('/'), |i| nom_remark_time(i, timestamp)),
+        ),
+    )
+    .map(|(direction, speed, time)| PeakWind {
+        direction,
+        speed,
+        time,
+    })
+    .parse(input)
+}
+
+pub(crate) fn nom_wind_shift<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, WindShift> {
+    preceded(
+        tag("WSHFT "),
+        (|i| nom_remark_time(i, timestamp), opt(tag(" FROPA"))),
+    )
+    .map(|(time, fropa)| WindShift {
+        time,
+        frontal_passage: fropa.is_some(),
+    })
+    .parse(input)
+}
+
+pub(crate) fn nom_rapid_pressure_change(input: &str) -> IResult<&str, RapidPressureChange> {
+    alt((
+        value(RapidPressureChange::RisingRapidly, tag("PRESRR")),
+        value(RapidPressureChange::FallingRapidly, tag("PRESFR")),
+    ))
+    .parse(input)
+}
+
+/// Visibility in remarks is given in statute miles without the `SM` suffix,
+/// e.g. `TWR VIS 1 1/2`.
+fn nom_remark_visibility(input: &str) -> IResult<&str, StatuteMilesVisibility> {
+    let whole = map(
+        (u32, opt(preceded(char(' '), nom_fraction))),
+        |(whole, fraction)| (Some(whole), fraction),
+    );
+    let fraction_only = map(nom_fraction, |fraction| (None, Some(fraction)));
+    (opt(nom_distance_modifier), alt((fraction_only, whole)))
+        .map(|(modifier, (whole, fraction))| StatuteMilesVisibility {
+            whole,
+            fraction,
+            modifier,
+        })
+        .parse(input)
+}
+
+pub(crate) fn nom_tower_visibility(input: &str) -> IResult<&str, StatuteMilesVisibility> {
+    preceded(tag("TWR VIS "), nom_remark_visibility).parse(input)
+}
+
+pub(crate) fn nom_surface_visibility(input: &str) -> IResult<&str, StatuteMilesVisibility> {
+    preceded(tag("SFC VIS "), nom_remark_visibility).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{optional_data::OptionalData::Data, units::timestamp::test_time};
+
+    #[test]
+    fn test_sea_level_pressure() {
+        let (_, slp) = nom_sea_level_pressure("SLP132").unwrap();
+        assert_eq!(slp, SeaLevelPressure(10132));
+        assert_eq!(slp.hectopascals(), 1013.2);
+        let (_, slp) = nom_sea_level_pressure("SLP982").unwrap();
+        assert_eq!(slp.hectopascals(), 998.2);
+    }
+
+    #[test]
+    fn test_precise_temperature() {
+        let (_, t) = nom_precise_temperature("T01280106").unwrap();
+        assert_eq!(
+            t,
+            PreciseTemperature {
+                temperature: 128,
+                dew_point: Some(106)
+            }
+        );
+        let (_, t) = nom_precise_temperature("T10051012").unwrap();
+        assert_eq!(t.celsius(), -0.5);
+        assert_eq!(t.dew_point_celsius(), Some(-1.2));
+    }
+
+    #[test]
+    fn test_peak_wind() {
+        let (rest, peak) = nom_peak_wind(
+            "PK WND 28045/1955 SLP132",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        )
+        .unwrap();
+        assert_eq!(rest, " SLP132");
+        assert_eq!(peak.direction, Track(Data(280)));
+        assert_eq!(peak.speed, 45);
+        assert_eq!(peak.time, test_time(2025, 6, 29, 19, 55));
+        let (_, peak) = nom_peak_wind(
+            "PK WND 280105/32",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        )
+        .unwrap();
+        assert_eq!(peak.speed, 105);
+        assert_eq!(peak.time, test_time(2025, 6, 29, 20, 32));
+    }
+
+    #[test]
+    fn test_wind_shift() {
+        let (_, shift) = nom_wind_shift(
+            "WSHFT 1715 FROPA",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        )
+        .unwrap();
+        assert!(shift.frontal_passage);
+        assert_eq!(shift.time.hour(), 17);
+    }
+
+    #[test]
+    fn test_remark_visibility() {
+        let (_, vis) = nom_tower_visibility("TWR VIS 1 1/2").unwrap();
+        assert_eq!(
+            vis,
+            StatuteMilesVisibility {
+                whole: Some(1),
+                fraction: Some((1, 2)),
+                modifier: None,
+            }
+        );
+        let (_, vis) = nom_surface_visibility("SFC VIS 1/4").unwrap();
+        assert_eq!(vis.whole, None);
+        assert_eq!(vis.fraction, Some((1, 4)));
+    }
+}
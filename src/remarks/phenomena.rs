@@ -0,0 +1,279 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{map, opt, value, verify},
+    multi::{many0, many1},
+    sequence::{preceded, terminated},
+};
+
+use crate::{
+    remarks::{
+        location::{PhenomenonLocation, nom_phenomenon_location},
+        nom_remark_time,
+    },
+    units::timestamp::Timestamp,
+    weather::{
+        WeatherDescriptor, WeatherPhenomenon, nom_weather_descriptor, nom_weather_phenomenon,
+    },
+};
+
+/// Beginning and ending times of precipitation and thunderstorms since the
+/// last report, e.g. `RAB15E30` or `TSB0159E30`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeatherEvents {
+    pub descriptor: Option<WeatherDescriptor>,
+    pub phenomena: Vec<WeatherPhenomenon>,
+    pub events: Vec<WeatherEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeatherEvent {
+    Began(Zoned),
+    Ended(Zoned),
+}
+
+/// A phenomenon reported in remarks together with where it is seen, e.g.
+/// `LTG DSNT NE-SE` or `CB OHD MOV E`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignificantPhenomenon {
+    pub phenomenon: Phenomenon,
+    pub location: PhenomenonLocation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Phenomenon {
+    Lightning(Lightning),
+    /// `TS`
+    Thunderstorm,
+    /// `CB`
+    Cumulonimbus,
+    /// `CBMAM`
+    CumulonimbusMammatus,
+    /// `TCU`
+    ToweringCumulus,
+    /// `ACC`: altocumulus castellanus.
+    AltocumulusCastellanus,
+    /// `VIRGA`
+    Virga,
+    /// `FC`
+    FunnelCloud,
+    /// `TORNADO`
+    Tornado,
+    /// `WATERSPOUT`
+    Waterspout,
+}
+
+/// `[OCNL|FRQ|CONS] LTG[IC][CC][CG][CA]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lightning {
+    pub frequency: Option<LightningFrequency>,
+    /// Empty if the type is not reported.
+    pub types: Vec<LightningType>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightningFrequency {
+    /// `OCNL`: less than one flash per minute.
+    Occasional,
+    /// `FRQ`: about one to six flashes per minute.
+    Frequent,
+    /// `CONS`: more than six flashes per minute.
+    Continuous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightningType {
+    /// `IC`
+    InCloud,
+    /// `CC`
+    CloudToCloud,
+    /// `CG`
+    CloudToGround,
+    /// `CA`
+    CloudToAir,
+}
+
+fn nom_weather_event<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, WeatherEvent> {
+    alt((
+        map(
+            preceded(char('B'), |i| nom_remark_time(i, timestamp)),
+            WeatherEvent::Began,
+        ),
+        map(
+            preceded(char('E'), |i| nom_remark_time(i, timestamp)),
+            WeatherEvent::Ended,
+        ),
+    ))
+    .parse(input)
+}
+
+fn nom_weather_events_segment<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, WeatherEvents> {
+    (
+        verify(
+            (opt(nom_weather_descriptor), many0(nom_weather_phenomenon)),
+            |(descriptor, phenomena)| descriptor.is_some() || !phenomena.is_empty(),
+        ),
+        many1(|i| nom_weather_event(i, timestamp)),
+    )
+        .map(|((descriptor, phenomena), events)| WeatherEvents {
+            descriptor,
+            phenomena,
+            events,
+        })
+        .parse(input)
+}
+
+/// Several weather types may be chained in one group, e.g. `RAB15E30SNB30`.
+pub(crate) fn nom_weather_events<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, Vec<WeatherEvents>> {
+    many1(|i| nom_weather_events_segment(i, timestamp)).parse(input)
+}
+
+fn nom_lightning_frequency(input: &str) -> IResult<&str, LightningFrequency> {
+    alt((
+        value(LightningFrequency::Occasional, tag("OCNL")),
+        value(LightningFrequency::Frequent, tag("FRQ")),
+        value(LightningFrequency::Continuous, tag("CONS")),
+    ))
+    .parse(input)
+}
+
+fn nom_lightning_type(input: &str) -> IResult<&str, LightningType> {
+    alt((
+        value(LightningType::InCloud, tag("IC")),
+        value(LightningType::CloudToCloud, tag("CC")),
+        value(LightningType::CloudToGround, tag("CG")),
+        value(LightningType::CloudToAir, tag("CA")),
+    ))
+    .parse(input)
+}
+
+fn nom_lightning(input: &str) -> IResult<&str, Lightning> {
+    (
+        opt(terminated(nom_lightning_frequency, char(' '))),
+        preceded(tag("LTG"), many0(nom_lightning_type)),
+    )
+        .map(|(frequency, types)| Lightning { frequency, types })
+        .parse(input)
+}
+
+/// Longer codes are listed first so that e.g. `CBMAM` is not read as `CB`.
+fn nom_phenomenon(input: &str) -> IResult<&str, Phenomenon> {
+    alt((
+        map(nom_lightning, Phenomenon::Lightning),
+        value(Phenomenon::CumulonimbusMammatus, tag("CBMAM")),
+        value(Phenomenon::Cumulonimbus, tag("CB")),
+        value(Phenomenon::ToweringCumulus, tag("TCU")),
+        value(Phenomenon::Thunderstorm, tag("TS")),
+        value(Phenomenon::AltocumulusCastellanus, tag("ACC")),
+        value(Phenomenon::Virga, tag("VIRGA")),
+        value(Phenomenon::FunnelCloud, tag("FC")),
+        value(Phenomenon::Tornado, tag("TORNADO")),
+        value(Phenomenon::Waterspout, tag("WATERSPOUT")),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_significant_phenomenon(input: &str) -> IResult<&str, SignificantPhenomenon> {
+    (nom_phenomenon, opt(nom_phenomenon_location))
+        .map(|(phenomenon, location)| SignificantPhenomenon {
+            phenomenon,
+            location: location.unwrap_or_default(),
+        })
+        .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        remarks::location::{DirectionRange, LocationDistance, Movement},
+        units::{compass::CompassOctant, timestamp::test_time},
+    };
+
+    #[test]
+    fn test_chained_weather_events() {
+        let (rest, events) = nom_weather_events(
+            "RAB15E30SNB30",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        )
+        .unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            events,
+            vec![
+                WeatherEvents {
+                    descriptor: None,
+                    phenomena: vec![WeatherPhenomenon::Rain],
+                    events: vec![
+                        WeatherEvent::Began(test_time(2025, 6, 29, 20, 15)),
+                        WeatherEvent::Ended(test_time(2025, 6, 29, 20, 30))
+                    ],
+                },
+                WeatherEvents {
+                    descriptor: None,
+                    phenomena: vec![WeatherPhenomenon::Snow],
+                    events: vec![WeatherEvent::Began(test_time(2025, 6, 29, 20, 30))],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thunderstorm_events() {
+        let (_, events) = nom_weather_events(
+            "TSB0159E30",
+            &Timestamp::new(test_time(2025, 6, 29, 20, 53)),
+        )
+        .unwrap();
+        assert_eq!(events[0].descriptor, Some(WeatherDescriptor::Thunderstorm));
+        assert_eq!(
+            events[0].events,
+            vec![
+                WeatherEvent::Began(test_time(2025, 6, 29, 1, 59)),
+                WeatherEvent::Ended(test_time(2025, 6, 29, 20, 30))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lightning() {
+        let (_, phenomenon) = nom_significant_phenomenon("FRQ LTGICCG DSNT NE-SE").unwrap();
+        assert_eq!(
+            phenomenon.phenomenon,
+            Phenomenon::Lightning(Lightning {
+                frequency: Some(LightningFrequency::Frequent),
+                types: vec![LightningType::InCloud, LightningType::CloudToGround],
+            })
+        );
+        assert_eq!(
+            phenomenon.location.directions,
+            vec![DirectionRange {
+                from: CompassOctant::NorthEast,
+                to: CompassOctant::SouthEast,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cumulonimbus_overhead() {
+        let (_, phenomenon) = nom_significant_phenomenon("CB OHD MOV E").unwrap();
+        assert_eq!(phenomenon.phenomenon, Phenomenon::Cumulonimbus);
+        assert_eq!(
+            phenomenon.location.distance,
+            Some(LocationDistance::Overhead)
+        );
+        assert_eq!(
+            phenomenon.location.movement,
+            Some(Movement::Towards(CompassOctant::East))
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use nom::{
+    IResult, Parser,
+    bytes::complete::{tag, take, take_while_m_n},
+    character::complete::{char, u32},
+    combinator::{all_consuming, map_parser, opt},
+    sequence::preceded,
+};
+
+use crate::{
+    optional_data::OptionalData,
+    pressure::{Pressure, PressureUnit},
+};
+
+const HECTOPASCALS_PER_MILLIMETER_OF_MERCURY: f64 = 1.333_224;
+
+/// `QFEmmm[/hhhh]`: pressure at aerodrome level, reported by stations in
+/// Russia and the CIS in millimetres of mercury and usually hectopascals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Qfe {
+    pub millimeters_of_mercury: u32,
+    pub hectopascals: Option<u32>,
+}
+
+impl Qfe {
+    /// The reported hectopascal value, or converted from millimetres of
+    /// mercury if only those are given.
+    pub fn hectopascals(&self) -> f64 {
+        match self.hectopascals {
+            Some(hectopascals) => f64::from(hectopascals),
+            None => f64::from(self.millimeters_of_mercury) * HECTOPASCALS_PER_MILLIMETER_OF_MERCURY,
+        }
+    }
+
+    /// QFE as a [`Pressure`] in whole hectopascals.
+    pub fn pressure(&self) -> Pressure {
+        Pressure {
+            value: OptionalData::Data(self.hectopascals().round() as u32),
+            unit: PressureUnit::Hectopascals,
+        }
+    }
+}
+
+pub(crate) fn nom_qfe(input: &str) -> IResult<&str, Qfe> {
+    preceded(
+        tag("QFE"),
+        (
+            map_parser(take(3usize), all_consuming(u32)),
+            opt(preceded(
+                char('/'),
+                map_parser(
+                    take_while_m_n(3, 4, |c: char| c.is_ascii_digit()),
+                    all_consuming(u32),
+                ),
+            )),
+        ),
+    )
+    .map(|(millimeters_of_mercury, hectopascals)| Qfe {
+        millimeters_of_mercury,
+        hectopascals,
+    })
+    .parse(input)
+}
+
+/// `QBBhhh`: height of the cloud base in metres.
+pub(crate) fn nom_cloud_base(input: &str) -> IResult<&str, u32> {
+    preceded(tag("QBB"), map_parser(take(3usize), all_consuming(u32))).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qfe() {
+        let (_, qfe) = nom_qfe("QFE747/0996").unwrap();
+        assert_eq!(
+            qfe,
+            Qfe {
+                millimeters_of_mercury: 747,
+                hectopascals: Some(996),
+            }
+        );
+        assert_eq!(qfe.pressure().value, OptionalData::Data(996));
+    }
+
+    #[test]
+    fn test_qfe_without_hectopascals() {
+        let (_, qfe) = nom_qfe("QFE750").unwrap();
+        assert_eq!(qfe.hectopascals, None);
+        assert_eq!(qfe.pressure().value, OptionalData::Data(1000));
+    }
+
+    #[test]
+    fn test_cloud_base() {
+        assert_eq!(nom_cloud_base("QBB180"), Ok(("", 180)));
+        assert!(nom_cloud_base("QBB18").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, u32},
+    combinator::map,
+    sequence::{preceded, separated_pair, terminated},
+};
+
+use crate::{
+    runway::nom_runway_designator,
+    wind::{Wind, nom_wind},
+};
+
+/// Wind remark as used by Norwegian stations, e.g. `WIND 1200FT 30015KT` or
+/// `WIND RWY 24 VRB03KT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemarkWind {
+    pub location: RemarkWindLocation,
+    pub wind: Wind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemarkWindLocation {
+    /// Height above the aerodrome in feet.
+    Height(u32),
+    Runway(String),
+}
+
+fn nom_remark_wind_location(input: &str) -> IResult<&str, RemarkWindLocation> {
+    alt((
+        map(terminated(u32, tag("FT")), RemarkWindLocation::Height),
+        map(
+            preceded(tag("RWY "), nom_runway_designator),
+            RemarkWindLocation::Runway,
+        ),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_remark_wind(input: &str) -> IResult<&str, RemarkWind> {
+    preceded(
+        tag("WIND "),
+        separated_pair(nom_remark_wind_location, char(' '), nom_wind),
+    )
+    .map(|(location, wind)| RemarkWind { location, wind })
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        optional_data::OptionalData::Data,
+        units::{
+            track::Track,
+            velocity::{VelocityUnit, WindVelocity},
+        },
+        wind::WindDirection,
+    };
+
+    #[test]
+    fn test_wind_aloft() {
+        let expected = RemarkWind {
+            location: RemarkWindLocation::Height(1200),
+            wind: Wind {
+                dir: WindDirection::Heading(Track(Data(300))),
+                speed: WindVelocity {
+                    velocity: Data(15),
+                    velocity_above_maximum: false,
+                    gust: None,
+                    gust_above_maximum: false,
+                    unit: VelocityUnit::Knots,
+                },
+                varying: None,
+            },
+        };
+        assert_eq!(nom_remark_wind("WIND 1200FT 30015KT"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_runway_wind() {
+        let (rest, wind) = nom_remark_wind("WIND RWY 24 VRB03KT WIND").unwrap();
+        assert_eq!(rest, " WIND");
+        assert_eq!(wind.location, RemarkWindLocation::Runway("24".to_string()));
+        assert_eq!(wind.wind.dir, WindDirection::Variable);
+    }
+}
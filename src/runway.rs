@@ -0,0 +1,359 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{char, one_of},
+    combinator::{map, map_opt, opt, recognize, value},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+};
+
+use crate::optional_data::{OptionalData, nom_digits};
+
+/// Wind shear reported along the take-off or approach paths.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WindShear {
+    AllRunways,
+    Runways(Vec<String>),
+}
+
+/// State of the runway surface, reported in winter conditions either as
+/// `R24/290195` or in the older eight digit form `24290195`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RunwayState {
+    /// `R/SNOCLO`: the aerodrome is closed due to snow on the runways.
+    SnowClosed,
+    Runway {
+        runway: RunwayIdentifier,
+        condition: RunwayCondition,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RunwayIdentifier {
+    Runway(String),
+    /// Code `88`.
+    AllRunways,
+    /// Code `99`: no new information, the previous report is repeated.
+    RepeatedFromPrevious,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RunwayCondition {
+    /// `CLRD`: contamination has ceased to exist.
+    Cleared {
+        friction: OptionalData<BrakingAction, 2>,
+    },
+    Contaminated {
+        deposit: OptionalData<RunwayDeposit, 1>,
+        extent: OptionalData<ContaminationExtent, 1>,
+        depth: OptionalData<DepositDepth, 2>,
+        friction: OptionalData<BrakingAction, 2>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunwayDeposit {
+    ClearAndDry,
+    Damp,
+    Wet,
+    RimeOrFrost,
+    DrySnow,
+    WetSnow,
+    Slush,
+    Ice,
+    CompactedSnow,
+    FrozenRuts,
+}
+
+/// Part of the runway covered by the deposit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContaminationExtent {
+    UpTo10Percent,
+    UpTo25Percent,
+    UpTo50Percent,
+    UpTo100Percent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepositDepth {
+    /// Code `00` is less than 1 mm. Codes `92`-`97` are converted from their
+    /// 5 cm steps, so `92` becomes 100 mm.
+    Millimeters(u32),
+    /// Code `98`.
+    FortyCentimetersOrMore,
+    /// Code `99`: runway not operational due to snow, slush, ice or large
+    /// drifts, or runway clearance in progress.
+    NotOperational,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrakingAction {
+    /// Measured friction coefficient in hundredths, codes `01`-`90`.
+    FrictionCoefficient(u32),
+    Poor,
+    MediumToPoor,
+    Medium,
+    MediumToGood,
+    Good,
+    /// Code `99`: figures unreliable.
+    Unreliable,
+}
+
+/// Runway designator as used in RVR and wind shear groups, e.g. `24`, `06L`.
+pub(crate) fn nom_runway_designator(input: &str) -> IResult<&str, String> {
+    map(
+        recognize((
+            take_while_m_n(2, 2, |c: char| c.is_ascii_digit()),
+            opt(one_of("LCR")),
+        )),
+        str::to_string,
+    )
+    .parse(input)
+}
+
+fn nom_wind_shear_runway(input: &str) -> IResult<&str, String> {
+    preceded(
+        (tag("WS "), alt((tag("RWY"), tag("R")))),
+        nom_runway_designator,
+    )
+    .parse(input)
+}
+
+pub(crate) fn nom_wind_shear(input: &str) -> IResult<&str, WindShear> {
+    alt((
+        value(WindShear::AllRunways, tag("WS ALL RWY")),
+        map(
+            separated_list1(char(' '), nom_wind_shear_runway),
+            WindShear::Runways,
+        ),
+    ))
+    .parse(input)
+}
+
+fn nom_runway_deposit(input: &str) -> IResult<&str, OptionalData<RunwayDeposit, 1>> {
+    OptionalData::optional_field(map_opt(nom_digits(1), |code| {
+        Some(match code {
+            0 => RunwayDeposit::ClearAndDry,
+            1 => RunwayDeposit::Damp,
+            2 => RunwayDeposit::Wet,
+            3 => RunwayDeposit::RimeOrFrost,
+            4 => RunwayDeposit::DrySnow,
+            5 => RunwayDeposit::WetSnow,
+            6 => RunwayDeposit::Slush,
+            7 => RunwayDeposit::Ice,
+            8 => RunwayDeposit::CompactedSnow,
+            9 => RunwayDeposit::FrozenRuts,
+            _ => return None,
+        })
+    }))
+    .parse(input)
+}
+
+fn nom_contamination_extent(input: &str) -> IResult<&str, OptionalData<ContaminationExtent, 1>> {
+    OptionalData::optional_field(map_opt(nom_digits(1), |code| match code {
+        1 => Some(ContaminationExtent::UpTo10Percent),
+        2 => Some(ContaminationExtent::UpTo25Percent),
+        5 => Some(ContaminationExtent::UpTo50Percent),
+        9 => Some(ContaminationExtent::UpTo100Percent),
+        _ => None,
+    }))
+    .parse(input)
+}
+
+fn nom_deposit_depth(input: &str) -> IResult<&str, OptionalData<DepositDepth, 2>> {
+    OptionalData::optional_field(map_opt(nom_digits(2), |code| match code {
+        0..=90 => Some(DepositDepth::Millimeters(code)),
+        92..=97 => Some(DepositDepth::Millimeters((code - 90) * 50)),
+        98 => Some(DepositDepth::FortyCentimetersOrMore),
+        99 => Some(DepositDepth::NotOperational),
+        _ => None,
+    }))
+    .parse(input)
+}
+
+fn nom_braking_action(input: &str) -> IResult<&str, OptionalData<BrakingAction, 2>> {
+    OptionalData::optional_field(map_opt(nom_digits(2), |code| match code {
+        1..=90 => Some(BrakingAction::FrictionCoefficient(code)),
+        91 => Some(BrakingAction::Poor),
+        92 => Some(BrakingAction::MediumToPoor),
+        93 => Some(BrakingAction::Medium),
+        94 => Some(BrakingAction::MediumToGood),
+        95 => Some(BrakingAction::Good),
+        99 => Some(BrakingAction::Unreliable),
+        _ => None,
+    }))
+    .parse(input)
+}
+
+fn nom_runway_condition(input: &str) -> IResult<&str, RunwayCondition> {
+    alt((
+        map(preceded(tag("CLRD"), nom_braking_action), |friction| {
+            RunwayCondition::Cleared { friction }
+        }),
+        map(
+            (
+                nom_runway_deposit,
+                nom_contamination_extent,
+                nom_deposit_depth,
+                nom_braking_action,
+            ),
+            |(deposit, extent, depth, friction)| RunwayCondition::Contaminated {
+                deposit,
+                extent,
+                depth,
+                friction,
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+fn nom_runway_identifier(input: &str) -> IResult<&str, RunwayIdentifier> {
+    map(nom_runway_designator, |runway| match runway.as_str() {
+        "88" => RunwayIdentifier::AllRunways,
+        "99" => RunwayIdentifier::RepeatedFromPrevious,
+        _ => RunwayIdentifier::Runway(runway),
+    })
+    .parse(input)
+}
+
+/// The eight digit form numbers right hand parallel runways by adding 50 to
+/// the designator, so `74` is runway `24R`.
+fn nom_legacy_runway_identifier(input: &str) -> IResult<&str, RunwayIdentifier> {
+    map_opt(nom_digits(2), |code| match code {
+        1..=36 => Some(RunwayIdentifier::Runway(format!("{code:02}"))),
+        51..=86 => Some(RunwayIdentifier::Runway(format!("{:02}R", code - 50))),
+        88 => Some(RunwayIdentifier::AllRunways),
+        99 => Some(RunwayIdentifier::RepeatedFromPrevious),
+        _ => None,
+    })
+    .parse(input)
+}
+
+pub(crate) fn nom_runway_state(input: &str) -> IResult<&str, RunwayState> {
+    alt((
+        value(
+            RunwayState::SnowClosed,
+            preceded(opt(tag("R/")), tag("SNOCLO")),
+        ),
+        map(
+            alt((
+                preceded(
+                    char('R'),
+                    separated_pair(nom_runway_identifier, char('/'), nom_runway_condition),
+                ),
+                (nom_legacy_runway_identifier, nom_runway_condition),
+            )),
+            |(runway, condition)| RunwayState::Runway { runway, condition },
+        ),
+    ))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runway_designator() {
+        assert_eq!(nom_runway_designator("24/"), Ok(("/", "24".to_string())));
+        assert_eq!(nom_runway_designator("06L"), Ok(("", "06L".to_string())));
+        assert!(nom_runway_designator("6L").is_err());
+    }
+
+    #[test]
+    fn test_wind_shear_all() {
+        assert_eq!(
+            nom_wind_shear("WS ALL RWY RMK"),
+            Ok((" RMK", WindShear::AllRunways))
+        );
+    }
+
+    #[test]
+    fn test_wind_shear_runways() {
+        assert_eq!(
+            nom_wind_shear("WS R24 WS RWY24L"),
+            Ok((
+                "",
+                WindShear::Runways(vec!["24".to_string(), "24L".to_string()])
+            ))
+        );
+    }
+
+    #[test]
+    fn test_runway_state() {
+        let expected = RunwayState::Runway {
+            runway: RunwayIdentifier::Runway("24".to_string()),
+            condition: RunwayCondition::Contaminated {
+                deposit: OptionalData::Data(RunwayDeposit::Wet),
+                extent: OptionalData::Data(ContaminationExtent::UpTo100Percent),
+                depth: OptionalData::Data(DepositDepth::Millimeters(1)),
+                friction: OptionalData::Data(BrakingAction::Good),
+            },
+        };
+        assert_eq!(nom_runway_state("R24/290195"), Ok(("", expected.clone())));
+        assert_eq!(nom_runway_state("24290195"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_legacy_right_runway() {
+        let (_, state) = nom_runway_state("74//9962").unwrap();
+        assert_eq!(
+            state,
+            RunwayState::Runway {
+                runway: RunwayIdentifier::Runway("24R".to_string()),
+                condition: RunwayCondition::Contaminated {
+                    deposit: OptionalData::Undefined,
+                    extent: OptionalData::Undefined,
+                    depth: OptionalData::Data(DepositDepth::NotOperational),
+                    friction: OptionalData::Data(BrakingAction::FrictionCoefficient(62)),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_special_depths() {
+        let (_, state) = nom_runway_state("R88/459291").unwrap();
+        let RunwayState::Runway { runway, condition } = state else {
+            panic!("expected runway state");
+        };
+        assert_eq!(runway, RunwayIdentifier::AllRunways);
+        assert_eq!(
+            condition,
+            RunwayCondition::Contaminated {
+                deposit: OptionalData::Data(RunwayDeposit::DrySnow),
+                extent: OptionalData::Data(ContaminationExtent::UpTo50Percent),
+                depth: OptionalData::Data(DepositDepth::Millimeters(100)),
+                friction: OptionalData::Data(BrakingAction::Poor),
+            }
+        );
+    }
+
+    #[test]
+    fn test_cleared_and_closed() {
+        assert_eq!(
+            nom_runway_state("R24/CLRD//"),
+            Ok((
+                "",
+                RunwayState::Runway {
+                    runway: RunwayIdentifier::Runway("24".to_string()),
+                    condition: RunwayCondition::Cleared {
+                        friction: OptionalData::Undefined
+                    },
+                }
+            ))
+        );
+        assert_eq!(
+            nom_runway_state("R/SNOCLO"),
+            Ok(("", RunwayState::SnowClosed))
+        );
+    }
+
+    #[test]
+    fn test_reserved_codes() {
+        assert!(nom_runway_state("R24/239195").is_err());
+        assert!(nom_runway_state("R24/291996").is_err());
+    }
+}
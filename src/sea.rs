@@ -0,0 +1,114 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::take,
+    character::complete::{char, u32},
+    combinator::{all_consuming, map, map_parser},
+    sequence::{preceded, separated_pair},
+};
+
+use crate::{
+    optional_data::{OptionalData, OptionalNumber},
+    temprature::nom_maybe_negative_temp,
+};
+
+/// Supplementary sea-surface group reported by offshore stations, e.g.
+/// `W12/S4` or `W15/H014`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeaState {
+    /// Sea-surface temperature in degrees Celsius.
+    pub temperature: OptionalData<i32, 2>,
+    pub surface: OptionalData<SeaSurface, 2>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeaSurface {
+    /// State of the sea, WMO code table 3700. `0` is calm (glassy) and `9`
+    /// is phenomenal.
+    StateOfSea(OptionalNumber<1>),
+    /// Significant wave height in decimetres.
+    WaveHeight(OptionalNumber<3>),
+}
+
+fn nom_sea_surface(input: &str) -> IResult<&str, SeaSurface> {
+    alt((
+        map(
+            preceded(
+                char('S'),
+                OptionalData::optional_field(map_parser(take(1usize), all_consuming(u32))),
+            ),
+            SeaSurface::StateOfSea,
+        ),
+        map(
+            preceded(
+                char('H'),
+                OptionalData::optional_field(map_parser(take(3usize), all_consuming(u32))),
+            ),
+            SeaSurface::WaveHeight,
+        ),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_sea_state(input: &str) -> IResult<&str, SeaState> {
+    preceded(
+        char('W'),
+        separated_pair(
+            OptionalData::optional_field(nom_maybe_negative_temp),
+            char('/'),
+            OptionalData::optional_field(nom_sea_surface),
+        ),
+    )
+    .map(|(temperature, surface)| SeaState {
+        temperature,
+        surface,
+    })
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optional_data::OptionalData::{Data, Undefined};
+
+    #[test]
+    fn test_state_of_sea() {
+        let expected = SeaState {
+            temperature: Data(10),
+            surface: Data(SeaSurface::StateOfSea(Data(4))),
+        };
+        assert_eq!(nom_sea_state("W10/S4"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_missing_temperature() {
+        let expected = SeaState {
+            temperature: Undefined,
+            surface: Data(SeaSurface::StateOfSea(Data(4))),
+        };
+        assert_eq!(nom_sea_state("W///S4"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_missing_state() {
+        let expected = SeaState {
+            temperature: Data(22),
+            surface: Undefined,
+        };
+        assert_eq!(nom_sea_state("W22///"), Ok(("", expected)));
+        let expected = SeaState {
+            temperature: Undefined,
+            surface: Undefined,
+        };
+        assert_eq!(nom_sea_state("W/////"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_wave_height() {
+        let expected = SeaState {
+            temperature: Data(-1),
+            surface: Data(SeaSurface::WaveHeight(Data(14))),
+        };
+        assert_eq!(nom_sea_state("WM01/H014"), Ok(("", expected)));
+    }
+}
@@ -0,0 +1,1061 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_till1},
+    character::complete::{
+        alpha1, alphanumeric1, char, multispace0, multispace1, one_of, space0, u32,
+    },
+    combinator::{eof, map, not, opt, peek, value, verify},
+    multi::{many_till, many1, separated_list1},
+    sequence::{delimited, preceded, separated_pair, terminated},
+};
+
+use crate::{
+    metar::nom_icao,
+    obscuration::CloudCoverage,
+    optional_data::nom_digits,
+    units::{
+        compass::{CompassOctant, CompassPoint, nom_compass_octant, nom_compass_point},
+        timestamp::{Timestamp, nom_day_time, parse_double_digit, reference_now},
+        velocity::VelocityUnit,
+    },
+    weather::{WeatherPhenomenon, nom_weather_phenomenon},
+};
+
+/// SIGMET or AIRMET message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sigmet {
+    pub raw: String,
+    pub kind: SigmetKind,
+    /// Air traffic services unit the message is for.
+    pub atsu: String,
+    /// Sequence number, e.g. `3` or `A02`.
+    pub sequence: String,
+    pub valid_from: Zoned,
+    pub valid_until: Zoned,
+    /// Meteorological watch office that issued the message.
+    pub mwo: String,
+    pub fir: Fir,
+    pub phenomenon: SigmetPhenomenon,
+    pub observation: Option<Observation>,
+    pub area: Option<SigmetArea>,
+    pub levels: Option<LevelBand>,
+    pub movement: Option<SigmetMovement>,
+    pub intensity_change: Option<IntensityChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigmetKind {
+    Sigmet,
+    Airmet,
+}
+
+/// Flight information region, e.g. `ENOR POLARIS FIR`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fir {
+    pub icao: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intensity {
+    /// `MOD`, AIRMET only.
+    Moderate,
+    /// `SEV`
+    Severe,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvectiveQualifier {
+    /// `OBSC`: obscured by haze or smoke.
+    Obscured,
+    /// `EMBD`: embedded in cloud layers.
+    Embedded,
+    /// `FRQ`: frequent, little or no separation.
+    Frequent,
+    /// `SQL`: squall line.
+    SquallLine,
+    /// `ISOL`, AIRMET only.
+    Isolated,
+    /// `OCNL`, AIRMET only.
+    Occasional,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvectiveCloud {
+    Thunderstorm,
+    /// `TSGR`
+    ThunderstormWithHail,
+    Cumulonimbus,
+    ToweringCumulus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SigmetPhenomenon {
+    Convective {
+        qualifier: ConvectiveQualifier,
+        cloud: ConvectiveCloud,
+    },
+    Turbulence(Intensity),
+    Icing {
+        intensity: Intensity,
+        /// `(FZRA)`: icing due to freezing rain.
+        freezing_rain: bool,
+    },
+    MountainWave(Intensity),
+    /// `MT OBSC`
+    MountainObscuration,
+    /// `HVY DS`
+    DustStorm,
+    /// `HVY SS`
+    Sandstorm,
+    /// `VA CLD` or `VA ERUPTION`
+    VolcanicAsh,
+    /// `RDOACT CLD`
+    RadioactiveCloud,
+    /// `TC` followed by the name of the cyclone.
+    TropicalCyclone(String),
+    /// `SFC WIND 270/40KT`, AIRMET only.
+    SurfaceWind {
+        direction: u32,
+        speed: Speed,
+    },
+    /// `SFC VIS 3000M (BR)`, AIRMET only.
+    SurfaceVisibility {
+        meters: u32,
+        causes: Vec<WeatherPhenomenon>,
+    },
+    /// `BKN CLD 100/1000FT` or `OVC CLD SFC/1000FT`, AIRMET only.
+    Cloud {
+        coverage: CloudCoverage,
+        base: Level,
+        top: Level,
+    },
+    /// `CNL SIGMET 2 291600/292000`: cancels an earlier message from the
+    /// same office.
+    Cancellation {
+        sequence: String,
+        valid_from: Zoned,
+        valid_until: Zoned,
+    },
+}
+
+/// `OBS` or `FCST`, optionally with `AT hhmmZ`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Observation {
+    Observed(Option<Zoned>),
+    Forecast(Option<Zoned>),
+}
+
+/// A position given as `N6030 E01045`, in signed arc minutes. South and west
+/// are negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coordinate {
+    pub latitude: i32,
+    pub longitude: i32,
+}
+
+impl Coordinate {
+    pub fn latitude_degrees(&self) -> f64 {
+        f64::from(self.latitude) / 60.0
+    }
+
+    pub fn longitude_degrees(&self) -> f64 {
+        f64::from(self.longitude) / 60.0
+    }
+}
+
+/// One side of a parallel or meridian, e.g. `N OF N60` or `W OF E01030`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boundary {
+    pub side: CompassOctant,
+    /// Latitude for north and south, longitude for east and west, in signed
+    /// arc minutes.
+    pub position: i32,
+}
+
+/// One side of a line, e.g. `NE OF LINE N6000 E00500 - N6100 E01000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideOfLine {
+    pub side: CompassOctant,
+    pub points: Vec<Coordinate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SigmetArea {
+    /// `WI` followed by the corners of the polygon.
+    Polygon(Vec<Coordinate>),
+    /// One or more sides of lines joined by `AND`.
+    Line(Vec<SideOfLine>),
+    /// One or more boundaries joined by `AND`.
+    Boundaries(Vec<Boundary>),
+    Point(Coordinate),
+    /// `ENTIRE FIR`, `ENTIRE UIR` or `ENTIRE CTA`.
+    Entire,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// `SFC`
+    Surface,
+    FlightLevel(u32),
+    Feet(u32),
+    Meters(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelBand {
+    /// `FL250/350`, `SFC/FL100` or `3000/9000FT`.
+    Between(Level, Level),
+    At(Level),
+    /// `TOP FL390`
+    Top(Level),
+    /// `TOP ABV FL390`
+    TopAbove(Level),
+    /// `ABV FL350`
+    Above(Level),
+    /// `BLW FL100`
+    Below(Level),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Speed {
+    pub value: u32,
+    pub unit: VelocityUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigmetMovement {
+    /// `MOV E 15KT` or `MOV NNE 20KT`
+    Moving {
+        direction: CompassPoint,
+        speed: Option<Speed>,
+    },
+    /// `STNR`
+    Stationary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntensityChange {
+    /// `INTSF`
+    Intensifying,
+    /// `WKN`
+    Weakening,
+    /// `NC`
+    NoChange,
+}
+
+fn nom_kind(input: &str) -> IResult<&str, SigmetKind> {
+    alt((
+        value(SigmetKind::Sigmet, tag("SIGMET")),
+        value(SigmetKind::Airmet, tag("AIRMET")),
+    ))
+    .parse(input)
+}
+
+/// `VALID ddhhmm/ddhhmm`
+fn nom_validity<'a>(input: &'a str, reference: &Timestamp) -> IResult<&'a str, (Zoned, Zoned)> {
+    preceded(
+        (tag("VALID"), multispace1),
+        separated_pair(
+            |i| nom_day_time(i, reference),
+            char('/'),
+            |i| nom_day_time(i, reference),
+        ),
+    )
+    .parse(input)
+}
+
+/// ICAO code, name and `FIR`, `UIR`, `FIR/UIR` or `CTA`.
+fn nom_fir(input: &str) -> IResult<&str, Fir> {
+    (
+        nom_icao,
+        many_till(
+            preceded(multispace1, take_till1(char::is_whitespace)),
+            preceded(
+                multispace1,
+                terminated(
+                    alt((tag("FIR/UIR"), tag("FIR"), tag("UIR"), tag("CTA"))),
+                    peek(alt((multispace1, eof))),
+                ),
+            ),
+        ),
+    )
+        .map(|(icao, (name, _))| Fir {
+            icao: icao.to_string(),
+            name: name.join(" "),
+        })
+        .parse(input)
+}
+
+fn nom_intensity(input: &str) -> IResult<&str, Intensity> {
+    alt((
+        value(Intensity::Moderate, tag("MOD")),
+        value(Intensity::Severe, tag("SEV")),
+    ))
+    .parse(input)
+}
+
+/// Two words separated by any whitespace, e.g. `MT OBSC`.
+fn nom_words<'a>(
+    first: &'static str,
+    second: &'static str,
+) -> impl Parser<&'a str, Output = (), Error = nom::error::Error<&'a str>> {
+    value((), (tag(first), multispace1, tag(second)))
+}
+
+fn nom_speed(input: &str) -> IResult<&str, Speed> {
+    (
+        u32,
+        alt((
+            value(VelocityUnit::Knots, tag("KT")),
+            value(VelocityUnit::KilometersPerHour, tag("KMH")),
+            value(VelocityUnit::MetersPerSecond, tag("MPS")),
+        )),
+    )
+        .map(|(value, unit)| Speed { value, unit })
+        .parse(input)
+}
+
+fn nom_phenomenon(input: &str) -> IResult<&str, SigmetPhenomenon> {
+    alt((
+        map(
+            separated_pair(
+                alt((
+                    value(ConvectiveQualifier::Obscured, tag("OBSC")),
+                    value(ConvectiveQualifier::Embedded, tag("EMBD")),
+                    value(ConvectiveQualifier::Frequent, tag("FRQ")),
+                    value(ConvectiveQualifier::SquallLine, tag("SQL")),
+                    value(ConvectiveQualifier::Isolated, tag("ISOL")),
+                    value(ConvectiveQualifier::Occasional, tag("OCNL")),
+                )),
+                multispace1,
+                alt((
+                    value(ConvectiveCloud::ThunderstormWithHail, tag("TSGR")),
+                    value(ConvectiveCloud::Thunderstorm, tag("TS")),
+                    value(ConvectiveCloud::Cumulonimbus, tag("CB")),
+                    value(ConvectiveCloud::ToweringCumulus, tag("TCU")),
+                )),
+            ),
+            |(qualifier, cloud)| SigmetPhenomenon::Convective { qualifier, cloud },
+        ),
+        map(
+            terminated(nom_intensity, (multispace1, tag("TURB"))),
+            SigmetPhenomenon::Turbulence,
+        ),
+        map(
+            (
+                terminated(nom_intensity, (multispace1, tag("ICE"))),
+                opt((multispace1, tag("(FZRA)"))),
+            ),
+            |(intensity, freezing_rain)| SigmetPhenomenon::Icing {
+                intensity,
+                freezing_rain: freezing_rain.is_some(),
+            },
+        ),
+        map(
+            terminated(nom_intensity, (multispace1, tag("MTW"))),
+            SigmetPhenomenon::MountainWave,
+        ),
+        value(
+            SigmetPhenomenon::MountainObscuration,
+            nom_words("MT", "OBSC"),
+        ),
+        value(SigmetPhenomenon::DustStorm, nom_words("HVY", "DS")),
+        value(SigmetPhenomenon::Sandstorm, nom_words("HVY", "SS")),
+        value(
+            SigmetPhenomenon::VolcanicAsh,
+            alt((nom_words("VA", "CLD"), nom_words("VA", "ERUPTION"))),
+        ),
+        value(
+            SigmetPhenomenon::RadioactiveCloud,
+            nom_words("RDOACT", "CLD"),
+        ),
+        map(
+            preceded((tag("TC"), multispace1), alphanumeric1),
+            |name: &str| SigmetPhenomenon::TropicalCyclone(name.to_string()),
+        ),
+        map(
+            preceded(
+                (nom_words("SFC", "WIND"), multispace1),
+                separated_pair(nom_digits(3), char('/'), nom_speed),
+            ),
+            |(direction, speed)| SigmetPhenomenon::SurfaceWind { direction, speed },
+        ),
+        map(
+            preceded(
+                (nom_words("SFC", "VIS"), multispace1),
+                separated_pair(
+                    terminated(u32, char('M')),
+                    multispace1,
+                    delimited(
+                        char('('),
+                        many1(preceded(space0, nom_weather_phenomenon)),
+                        char(')'),
+                    ),
+                ),
+            ),
+            |(meters, causes)| SigmetPhenomenon::SurfaceVisibility { meters, causes },
+        ),
+        map(
+            separated_pair(
+                alt((
+                    value(CloudCoverage::Broken, tag("BKN")),
+                    value(CloudCoverage::Overcast, tag("OVC")),
+                )),
+                (multispace1, tag("CLD"), multispace1),
+                nom_between,
+            ),
+            |(coverage, (base, top))| SigmetPhenomenon::Cloud {
+                coverage,
+                base,
+                top,
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+/// `CNL SIGMET 2 291600/292000`
+fn nom_cancellation<'a>(
+    input: &'a str,
+    reference: &Timestamp,
+) -> IResult<&'a str, SigmetPhenomenon> {
+    preceded(
+        (tag("CNL"), multispace1, nom_kind, multispace1),
+        separated_pair(
+            take_till1(char::is_whitespace),
+            multispace1,
+            separated_pair(
+                |i| nom_day_time(i, reference),
+                char('/'),
+                |i| nom_day_time(i, reference),
+            ),
+        ),
+    )
+    .map(
+        |(sequence, (valid_from, valid_until))| SigmetPhenomenon::Cancellation {
+            sequence: sequence.to_string(),
+            valid_from,
+            valid_until,
+        },
+    )
+    .parse(input)
+}
+
+/// `hhmmZ`, resolved to the time closest to the start of the validity period.
+fn nom_time_near<'a>(input: &'a str, valid_from: &Zoned) -> IResult<&'a str, Zoned> {
+    let reference = Timestamp::new(valid_from.clone());
+    map(
+        terminated(
+            verify(
+                (parse_double_digit, parse_double_digit),
+                |(hour, minute)| (0..24).contains(hour) && (0..60).contains(minute),
+            ),
+            char('Z'),
+        ),
+        |(hour, minute)| {
+            let before = reference.previous_time(Some(hour), minute);
+            let after = reference.next_time_of_day(hour, minute);
+            if valid_from.duration_until(&after) < before.duration_until(valid_from) {
+                after
+            } else {
+                before
+            }
+        },
+    )
+    .parse(input)
+}
+
+fn nom_observation<'a>(input: &'a str, valid_from: &Zoned) -> IResult<&'a str, Observation> {
+    let at = |i| {
+        opt(preceded((multispace1, tag("AT"), multispace1), |i| {
+            nom_time_near(i, valid_from)
+        }))
+        .parse(i)
+    };
+    alt((
+        map(preceded(tag("OBS"), at), Observation::Observed),
+        map(preceded(tag("FCST"), at), Observation::Forecast),
+    ))
+    .parse(input)
+}
+
+/// Degrees with `digits` digits and optional minutes, in signed arc minutes.
+/// Fails if the position is beyond `max_degrees`.
+fn nom_angle(
+    hemispheres: &'static str,
+    digits: usize,
+    max_degrees: u32,
+) -> impl FnMut(&str) -> IResult<&str, i32> {
+    move |input| {
+        map(
+            verify(
+                (one_of(hemispheres), nom_digits(digits), opt(nom_digits(2))),
+                |&(_, degrees, minutes)| {
+                    let minutes = minutes.unwrap_or(0);
+                    minutes < 60 && degrees * 60 + minutes <= max_degrees * 60
+                },
+            ),
+            |(hemisphere, degrees, minutes)| {
+                let minutes = (degrees * 60 + minutes.unwrap_or(0)) as i32;
+                if hemispheres.starts_with(hemisphere) {
+                    minutes
+                } else {
+                    -minutes
+                }
+            },
+        )
+        .parse(input)
+    }
+}
+
+/// `Ndd(mm)` or `Sdd(mm)`, in signed arc minutes.
+fn nom_latitude(input: &str) -> IResult<&str, i32> {
+    nom_angle("NS", 2, 90).parse(input)
+}
+
+/// `Eddd(mm)` or `Wddd(mm)`, in signed arc minutes.
+fn nom_longitude(input: &str) -> IResult<&str, i32> {
+    nom_angle("EW", 3, 180).parse(input)
+}
+
+fn nom_coordinate(input: &str) -> IResult<&str, Coordinate> {
+    separated_pair(nom_latitude, multispace1, nom_longitude)
+        .map(|(latitude, longitude)| Coordinate {
+            latitude,
+            longitude,
+        })
+        .parse(input)
+}
+
+/// Points separated by `-`, with or without whitespace around it.
+fn nom_coordinates(input: &str) -> IResult<&str, Vec<Coordinate>> {
+    separated_list1(
+        delimited(multispace0, char('-'), multispace0),
+        nom_coordinate,
+    )
+    .parse(input)
+}
+
+fn nom_boundary(input: &str) -> IResult<&str, Boundary> {
+    alt((
+        map(
+            separated_pair(
+                verify(nom_compass_octant, |side| {
+                    matches!(side, CompassOctant::North | CompassOctant::South)
+                }),
+                (multispace1, tag("OF"), multispace1),
+                nom_latitude,
+            ),
+            |(side, position)| Boundary { side, position },
+        ),
+        map(
+            separated_pair(
+                verify(nom_compass_octant, |side| {
+                    matches!(side, CompassOctant::East | CompassOctant::West)
+                }),
+                (multispace1, tag("OF"), multispace1),
+                nom_longitude,
+            ),
+            |(side, position)| Boundary { side, position },
+        ),
+    ))
+    .parse(input)
+}
+
+fn nom_and(input: &str) -> IResult<&str, ()> {
+    value((), (multispace1, tag("AND"), multispace1)).parse(input)
+}
+
+fn nom_side_of_line(input: &str) -> IResult<&str, SideOfLine> {
+    separated_pair(
+        nom_compass_octant,
+        (multispace1, nom_words("OF", "LINE"), multispace1),
+        nom_coordinates,
+    )
+    .map(|(side, points)| SideOfLine { side, points })
+    .parse(input)
+}
+
+fn nom_area(input: &str) -> IResult<&str, SigmetArea> {
+    alt((
+        map(
+            preceded(
+                (tag("WI"), multispace1),
+                verify(nom_coordinates, |points: &Vec<_>| points.len() >= 3),
+            ),
+            SigmetArea::Polygon,
+        ),
+        map(separated_list1(nom_and, nom_side_of_line), SigmetArea::Line),
+        map(
+            separated_list1(nom_and, nom_boundary),
+            SigmetArea::Boundaries,
+        ),
+        map(nom_coordinate, SigmetArea::Point),
+        value(
+            SigmetArea::Entire,
+            preceded(
+                (tag("ENTIRE"), multispace1),
+                alt((tag("FIR/UIR"), tag("FIR"), tag("UIR"), tag("CTA"))),
+            ),
+        ),
+    ))
+    .parse(input)
+}
+
+fn nom_level(input: &str) -> IResult<&str, Level> {
+    alt((
+        value(Level::Surface, tag("SFC")),
+        map(preceded(tag("FL"), u32), Level::FlightLevel),
+        map(terminated(u32, tag("FT")), Level::Feet),
+        map(terminated(u32, char('M')), Level::Meters),
+    ))
+    .parse(input)
+}
+
+/// A band between two levels. The unit may be given only once, as in
+/// `FL250/350` or `3000/9000FT`.
+fn nom_between(input: &str) -> IResult<&str, (Level, Level)> {
+    let pair = || separated_pair(u32, char('/'), u32);
+    alt((
+        separated_pair(nom_level, char('/'), nom_level),
+        map(preceded(tag("FL"), pair()), |(lower, upper)| {
+            (Level::FlightLevel(lower), Level::FlightLevel(upper))
+        }),
+        map(terminated(pair(), tag("FT")), |(lower, upper)| {
+            (Level::Feet(lower), Level::Feet(upper))
+        }),
+        map(terminated(pair(), char('M')), |(lower, upper)| {
+            (Level::Meters(lower), Level::Meters(upper))
+        }),
+    ))
+    .parse(input)
+}
+
+fn nom_level_band(input: &str) -> IResult<&str, LevelBand> {
+    alt((
+        map(
+            preceded((nom_words("TOP", "ABV"), multispace1), nom_level),
+            LevelBand::TopAbove,
+        ),
+        map(
+            preceded((tag("TOP"), multispace1), nom_level),
+            LevelBand::Top,
+        ),
+        map(
+            preceded((tag("ABV"), multispace1), nom_level),
+            LevelBand::Above,
+        ),
+        map(
+            preceded((tag("BLW"), multispace1), nom_level),
+            LevelBand::Below,
+        ),
+        map(nom_between, |(lower, upper)| {
+            LevelBand::Between(lower, upper)
+        }),
+        map(nom_level, LevelBand::At),
+    ))
+    .parse(input)
+}
+
+fn nom_movement(input: &str) -> IResult<&str, SigmetMovement> {
+    alt((
+        map(
+            preceded(
+                (tag("MOV"), multispace1),
+                (
+                    terminated(nom_compass_point, not(alpha1)),
+                    opt(preceded(multispace1, nom_speed)),
+                ),
+            ),
+            |(direction, speed)| SigmetMovement::Moving { direction, speed },
+        ),
+        value(SigmetMovement::Stationary, tag("STNR")),
+    ))
+    .parse(input)
+}
+
+fn nom_intensity_change(input: &str) -> IResult<&str, IntensityChange> {
+    alt((
+        value(IntensityChange::Intensifying, tag("INTSF")),
+        value(IntensityChange::Weakening, tag("WKN")),
+        value(IntensityChange::NoChange, tag("NC")),
+    ))
+    .parse(input)
+}
+
+/// Parses a SIGMET or AIRMET with the validity period resolved against the
+/// current time, allowing for a slightly slow clock. See
+/// [`nom_parse_sigmet_at`].
+pub fn nom_parse_sigmet(input: &str) -> IResult<&str, Sigmet> {
+    nom_parse_sigmet_at(input, &Timestamp::new(reference_now()))
+}
+
+/// Parses a SIGMET or AIRMET, resolving the validity period against
+/// `reference`, typically the time of the bulletin header. The level band may
+/// come before or after the area, but not both.
+pub fn nom_parse_sigmet_at<'a>(input: &'a str, reference: &Timestamp) -> IResult<&'a str, Sigmet> {
+    let (rest, (atsu, kind, sequence)) = (
+        nom_icao,
+        preceded(multispace1, nom_kind),
+        preceded(multispace1, take_till1(char::is_whitespace)),
+    )
+        .parse(input)?;
+    let (rest, (valid_from, valid_until)) =
+        preceded(multispace1, |i| nom_validity(i, reference)).parse(rest)?;
+    let (rest, (mwo, fir, phenomenon)) = (
+        preceded(multispace1, terminated(nom_icao, char('-'))),
+        preceded(multispace0, nom_fir),
+        preceded(
+            multispace1,
+            alt((|i| nom_cancellation(i, reference), nom_phenomenon)),
+        ),
+    )
+        .parse(rest)?;
+    let (rest, (observation, levels_before, area)) = (
+        opt(preceded(multispace1, |i| nom_observation(i, &valid_from))),
+        opt(preceded(multispace1, nom_level_band)),
+        opt(preceded(multispace1, nom_area)),
+    )
+        .parse(rest)?;
+    let after_area = rest;
+    let (rest, levels_after) = opt(preceded(multispace1, nom_level_band)).parse(rest)?;
+    if levels_before.is_some() && levels_after.is_some() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            after_area,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (rest, (movement, intensity_change)) = (
+        opt(preceded(multispace1, nom_movement)),
+        opt(preceded(multispace1, nom_intensity_change)),
+    )
+        .parse(rest)?;
+    let (rest, _) = opt(preceded(multispace0, char('='))).parse(rest)?;
+    Ok((
+        rest,
+        Sigmet {
+            raw: input[..input.len() - rest.len()].to_string(),
+            kind,
+            atsu: atsu.to_string(),
+            sequence: sequence.to_string(),
+            valid_from,
+            valid_until,
+            mwo: mwo.to_string(),
+            fir,
+            phenomenon,
+            observation,
+            area,
+            levels: levels_before.or(levels_after),
+            movement,
+            intensity_change,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::timestamp::test_time;
+
+    #[test]
+    fn test_coordinates() {
+        assert_eq!(
+            nom_coordinates("N6030 E01045 - S12 W00130-N60 E010"),
+            Ok((
+                "",
+                vec![
+                    Coordinate {
+                        latitude: 3630,
+                        longitude: 645
+                    },
+                    Coordinate {
+                        latitude: -720,
+                        longitude: -90
+                    },
+                    Coordinate {
+                        latitude: 3600,
+                        longitude: 600
+                    },
+                ]
+            ))
+        );
+        for input in [
+            "N9100 E01000",
+            "N6060 E01000",
+            "N6000 E18030",
+            "S9001 W00000",
+        ] {
+            assert!(nom_coordinate(input).is_err(), "{input}");
+        }
+        assert_eq!(
+            nom_coordinate("S90 W180"),
+            Ok((
+                "",
+                Coordinate {
+                    latitude: -5400,
+                    longitude: -10800
+                }
+            ))
+        );
+        let (_, coordinate) = nom_coordinate("N6030 E01045").unwrap();
+        assert_eq!(coordinate.latitude_degrees(), 60.5);
+        assert_eq!(coordinate.longitude_degrees(), 10.75);
+    }
+
+    #[test]
+    fn test_level_band() {
+        let cases = [
+            (
+                "FL250/350",
+                LevelBand::Between(Level::FlightLevel(250), Level::FlightLevel(350)),
+            ),
+            (
+                "SFC/FL100",
+                LevelBand::Between(Level::Surface, Level::FlightLevel(100)),
+            ),
+            (
+                "3000/9000FT",
+                LevelBand::Between(Level::Feet(3000), Level::Feet(9000)),
+            ),
+            (
+                "TOP ABV FL390",
+                LevelBand::TopAbove(Level::FlightLevel(390)),
+            ),
+            ("BLW FL100", LevelBand::Below(Level::FlightLevel(100))),
+            ("FL300", LevelBand::At(Level::FlightLevel(300))),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(nom_level_band(input), Ok(("", expected)), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_area() {
+        assert_eq!(
+            nom_area("N OF N60 AND W OF E01030"),
+            Ok((
+                "",
+                SigmetArea::Boundaries(vec![
+                    Boundary {
+                        side: CompassOctant::North,
+                        position: 3600
+                    },
+                    Boundary {
+                        side: CompassOctant::West,
+                        position: 630
+                    },
+                ])
+            ))
+        );
+        let (_, area) = nom_area("NE OF LINE N6000 E00500 - N6100 E01000").unwrap();
+        assert!(matches!(
+            area,
+            SigmetArea::Line(ref sides)
+                if sides.len() == 1 && sides[0].side == CompassOctant::NorthEast
+        ));
+        let (rest, area) = nom_area(
+            "N OF LINE N6000 E00500 - N6000 E01000 AND S OF LINE N6300 E00500 - N6300 E01000",
+        )
+        .unwrap();
+        assert_eq!(rest, "");
+        let SigmetArea::Line(sides) = area else {
+            panic!("expected line");
+        };
+        assert_eq!(
+            sides.iter().map(|line| line.side).collect::<Vec<_>>(),
+            [CompassOctant::North, CompassOctant::South]
+        );
+        assert_eq!(sides[1].points[0].latitude, 3780);
+        assert_eq!(nom_area("ENTIRE FIR"), Ok(("", SigmetArea::Entire)));
+    }
+
+    #[test]
+    fn test_parse_sigmet() {
+        let input = "ENOR SIGMET 3 VALID 291800/292200 ENMI- ENOR POLARIS FIR SEV TURB FCST AT 1800Z WI N6030 E01045 - N6200 E01200 - N6100 E00800 - N6030 E01045 FL250/350 MOV E 15KT NC=";
+        let (rest, sigmet) =
+            nom_parse_sigmet_at(input, &Timestamp::new(test_time(2025, 6, 29, 17, 45))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(sigmet.kind, SigmetKind::Sigmet);
+        assert_eq!(sigmet.atsu, "ENOR");
+        assert_eq!(sigmet.sequence, "3");
+        assert_eq!(sigmet.valid_from, test_time(2025, 6, 29, 18, 0));
+        assert_eq!(sigmet.valid_until, test_time(2025, 6, 29, 22, 0));
+        assert_eq!(sigmet.mwo, "ENMI");
+        assert_eq!(
+            sigmet.fir,
+            Fir {
+                icao: "ENOR".to_string(),
+                name: "POLARIS".to_string()
+            }
+        );
+        assert_eq!(
+            sigmet.phenomenon,
+            SigmetPhenomenon::Turbulence(Intensity::Severe)
+        );
+        assert_eq!(
+            sigmet.observation,
+            Some(Observation::Forecast(Some(test_time(2025, 6, 29, 18, 0))))
+        );
+        let Some(SigmetArea::Polygon(points)) = &sigmet.area else {
+            panic!("expected polygon");
+        };
+        assert_eq!(points.len(), 4);
+        assert_eq!(
+            sigmet.levels,
+            Some(LevelBand::Between(
+                Level::FlightLevel(250),
+                Level::FlightLevel(350)
+            ))
+        );
+        assert_eq!(
+            sigmet.movement,
+            Some(SigmetMovement::Moving {
+                direction: CompassPoint::East,
+                speed: Some(Speed {
+                    value: 15,
+                    unit: VelocityUnit::Knots
+                })
+            })
+        );
+        assert_eq!(sigmet.intensity_change, Some(IntensityChange::NoChange));
+    }
+
+    #[test]
+    fn test_parse_airmet() {
+        let input = "ESAA AIRMET A02 VALID 300000/300400 ESSA- ESAA SWEDEN FIR MOD ICE (FZRA) OBS AT 2350Z N OF N5930 SFC/3000FT STNR WKN";
+        let (rest, airmet) =
+            nom_parse_sigmet_at(input, &Timestamp::new(test_time(2025, 6, 29, 17, 45))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(airmet.kind, SigmetKind::Airmet);
+        assert_eq!(airmet.sequence, "A02");
+        assert_eq!(airmet.valid_from, test_time(2025, 6, 30, 0, 0));
+        assert_eq!(
+            airmet.phenomenon,
+            SigmetPhenomenon::Icing {
+                intensity: Intensity::Moderate,
+                freezing_rain: true
+            }
+        );
+        assert_eq!(
+            airmet.observation,
+            Some(Observation::Observed(Some(test_time(2025, 6, 29, 23, 50))))
+        );
+        assert_eq!(
+            airmet.levels,
+            Some(LevelBand::Between(Level::Surface, Level::Feet(3000)))
+        );
+        assert_eq!(airmet.movement, Some(SigmetMovement::Stationary));
+        assert_eq!(airmet.intensity_change, Some(IntensityChange::Weakening));
+    }
+
+    #[test]
+    fn test_line_breaks_between_fields() {
+        let input = "ENOR SIGMET 3 VALID 291800/292200 ENMI-\nENOR POLARIS FIR\nSEV TURB FCST WI N6030 E01045 -\nN6200 E01200 - N6100 E00800 FL250/350\nSTNR NC=";
+        let (rest, sigmet) =
+            nom_parse_sigmet_at(input, &Timestamp::new(test_time(2025, 6, 29, 17, 45))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(sigmet.fir.name, "POLARIS");
+        assert!(matches!(sigmet.area, Some(SigmetArea::Polygon(ref points)) if points.len() == 3));
+        assert_eq!(sigmet.intensity_change, Some(IntensityChange::NoChange));
+    }
+
+    #[test]
+    fn test_airmet_phenomena() {
+        let cases = [
+            (
+                "SFC WIND 270/40KT",
+                SigmetPhenomenon::SurfaceWind {
+                    direction: 270,
+                    speed: Speed {
+                        value: 40,
+                        unit: VelocityUnit::Knots,
+                    },
+                },
+            ),
+            (
+                "SFC VIS 3000M (BR)",
+                SigmetPhenomenon::SurfaceVisibility {
+                    meters: 3000,
+                    causes: vec![WeatherPhenomenon::Mist],
+                },
+            ),
+            (
+                "BKN CLD 100/1000FT",
+                SigmetPhenomenon::Cloud {
+                    coverage: CloudCoverage::Broken,
+                    base: Level::Feet(100),
+                    top: Level::Feet(1000),
+                },
+            ),
+            (
+                "OVC CLD SFC/1000FT",
+                SigmetPhenomenon::Cloud {
+                    coverage: CloudCoverage::Overcast,
+                    base: Level::Surface,
+                    top: Level::Feet(1000),
+                },
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(nom_phenomenon(input), Ok(("", expected)), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let input =
+            "ENOR SIGMET 4 VALID 291900/292200 ENMI- ENOR POLARIS FIR CNL SIGMET 2 291600/292000=";
+        let (rest, sigmet) =
+            nom_parse_sigmet_at(input, &Timestamp::new(test_time(2025, 6, 29, 18, 55))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            sigmet.phenomenon,
+            SigmetPhenomenon::Cancellation {
+                sequence: "2".to_string(),
+                valid_from: test_time(2025, 6, 29, 16, 0),
+                valid_until: test_time(2025, 6, 29, 20, 0),
+            }
+        );
+        assert_eq!(sigmet.area, None);
+    }
+
+    #[test]
+    fn test_raw_is_consumed_input() {
+        let message =
+            "ENOR SIGMET 4 VALID 291900/292200 ENMI- ENOR POLARIS FIR CNL SIGMET 2 291600/292000=";
+        let input = format!("{message}\nENOR SIGMET 5");
+        let (rest, sigmet) =
+            nom_parse_sigmet_at(&input, &Timestamp::new(test_time(2025, 6, 29, 18, 55))).unwrap();
+        assert_eq!(rest, "\nENOR SIGMET 5");
+        assert_eq!(sigmet.raw, message);
+    }
+
+    #[test]
+    fn test_sixteen_point_movement() {
+        let input = "ENOR SIGMET 3 VALID 291800/292200 ENMI- ENOR POLARIS FIR SEV TURB FCST N OF N60 FL250/350 MOV NNE 20KT NC=";
+        let (rest, sigmet) =
+            nom_parse_sigmet_at(input, &Timestamp::new(test_time(2025, 6, 29, 17, 45))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            sigmet.movement,
+            Some(SigmetMovement::Moving {
+                direction: CompassPoint::NorthNorthEast,
+                speed: Some(Speed {
+                    value: 20,
+                    unit: VelocityUnit::Knots
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn test_two_level_bands() {
+        let input = "ENOR SIGMET 3 VALID 291800/292200 ENMI- ENOR POLARIS FIR SEV TURB FCST FL250/350 N OF N60 FL100/200 STNR NC=";
+        assert!(
+            nom_parse_sigmet_at(input, &Timestamp::new(test_time(2025, 6, 29, 17, 45))).is_err()
+        );
+    }
+}
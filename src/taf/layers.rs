@@ -0,0 +1,129 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::take,
+    character::complete::{char, one_of, u32},
+    combinator::{all_consuming, map, map_parser},
+    sequence::preceded,
+};
+
+/// Forecast icing layer, `6Ihhht`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IcingLayer {
+    /// Icing type, WMO code table 1734.
+    pub code: u32,
+    /// Base of the layer in feet.
+    pub base: u32,
+    /// Top of the layer in feet.
+    pub top: u32,
+}
+
+/// Forecast turbulence layer, `5Bhhht`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TurbulenceLayer {
+    /// Turbulence type, WMO code table 0300.
+    pub code: u32,
+    /// Base of the layer in feet.
+    pub base: u32,
+    /// Top of the layer in feet.
+    pub top: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    None,
+    Light,
+    Moderate,
+    Severe,
+}
+
+impl IcingLayer {
+    pub fn severity(&self) -> Severity {
+        match self.code {
+            0 => Severity::None,
+            1..=3 => Severity::Light,
+            4..=6 => Severity::Moderate,
+            _ => Severity::Severe,
+        }
+    }
+}
+
+impl TurbulenceLayer {
+    pub fn severity(&self) -> Severity {
+        match self.code {
+            0 => Severity::None,
+            1 => Severity::Light,
+            2..=5 => Severity::Moderate,
+            _ => Severity::Severe,
+        }
+    }
+
+    /// Turbulence in cloud, as opposed to clear air turbulence.
+    pub fn in_cloud(&self) -> bool {
+        matches!(self.code, 4 | 5 | 8 | 9)
+    }
+}
+
+pub(crate) enum Layer {
+    Icing(IcingLayer),
+    Turbulence(TurbulenceLayer),
+}
+
+/// Type digit, base in hundreds of feet and thickness in thousands of feet.
+fn nom_layer(input: &str) -> IResult<&str, (u32, u32, u32)> {
+    (
+        one_of("0123456789"),
+        map_parser(take(3usize), all_consuming(u32)),
+        one_of("0123456789"),
+    )
+        .map(|(code, base, thickness)| {
+            let base = base * 100;
+            let thickness = thickness.to_digit(10).unwrap() * 1000;
+            (code.to_digit(10).unwrap(), base, base + thickness)
+        })
+        .parse(input)
+}
+
+pub(crate) fn nom_icing_or_turbulence(input: &str) -> IResult<&str, Layer> {
+    alt((
+        map(preceded(char('6'), nom_layer), |(code, base, top)| {
+            Layer::Icing(IcingLayer { code, base, top })
+        }),
+        map(preceded(char('5'), nom_layer), |(code, base, top)| {
+            Layer::Turbulence(TurbulenceLayer { code, base, top })
+        }),
+    ))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icing() {
+        let Ok(("", Layer::Icing(icing))) = nom_icing_or_turbulence("620304") else {
+            panic!("expected icing layer");
+        };
+        assert_eq!(
+            icing,
+            IcingLayer {
+                code: 2,
+                base: 3000,
+                top: 7000
+            }
+        );
+        assert_eq!(icing.severity(), Severity::Light);
+    }
+
+    #[test]
+    fn test_turbulence() {
+        let Ok(("", Layer::Turbulence(turbulence))) = nom_icing_or_turbulence("580102") else {
+            panic!("expected turbulence layer");
+        };
+        assert_eq!(turbulence.base, 1000);
+        assert_eq!(turbulence.top, 3000);
+        assert_eq!(turbulence.severity(), Severity::Severe);
+        assert!(turbulence.in_cloud());
+    }
+}
@@ -0,0 +1,337 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{map, map_opt, opt, value, verify},
+    multi::many0,
+    sequence::{preceded, separated_pair, terminated},
+};
+
+use crate::{
+    metar::{ReportModifier, nom_icao, nom_report_modifier},
+    taf::{
+        layers::{IcingLayer, Layer, TurbulenceLayer, nom_icing_or_turbulence},
+        temperature::{ForecastTemperature, nom_forecast_temperature},
+    },
+    trend::{ForecastConditions, nom_forecast_conditions},
+    units::timestamp::{
+        Timestamp, nom_day_time, nom_metar_timestamp_at, parse_double_digit, reference_now,
+    },
+};
+
+pub mod layers;
+pub mod temperature;
+pub mod timeline;
+
+/// Terminal aerodrome forecast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Taf {
+    pub raw: String,
+    pub modifier: Option<ReportModifier>,
+    pub icao: String,
+    pub issued: Timestamp,
+    pub valid_from: Zoned,
+    pub valid_until: Zoned,
+    /// Conditions at the start of the validity period.
+    pub conditions: ForecastConditions,
+    pub icing: Vec<IcingLayer>,
+    pub turbulence: Vec<TurbulenceLayer>,
+    /// `TX` and `TN` groups.
+    pub temperatures: Vec<ForecastTemperature>,
+    pub changes: Vec<TafChange>,
+}
+
+/// A change group. `FM` groups only have a start time, they last until the
+/// next `FM` group or the end of the forecast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TafChange {
+    pub change: TafChangeType,
+    /// `PROB30` or `PROB40`, in percent.
+    pub probability: Option<u32>,
+    pub from: Zoned,
+    pub until: Option<Zoned>,
+    pub conditions: ForecastConditions,
+    pub icing: Vec<IcingLayer>,
+    pub turbulence: Vec<TurbulenceLayer>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TafChangeType {
+    /// `FM`: the conditions are replaced entirely.
+    From,
+    /// `BECMG`: gradual change during the period.
+    Becoming,
+    /// `TEMPO`: temporary fluctuations during the period.
+    Temporary,
+    /// `PROBnn` without `TEMPO`.
+    Probable,
+}
+
+fn nom_day_hour<'a>(
+    timestamp: &Timestamp,
+) -> impl Parser<&'a str, Output = Zoned, Error = nom::error::Error<&'a str>> {
+    map_opt(
+        verify((parse_double_digit, parse_double_digit), |(_, hour)| {
+            (0..=24).contains(hour)
+        }),
+        |(day, hour)| timestamp.nearest_day_time(day, hour, 0),
+    )
+}
+
+/// `ddhh/ddhh`
+fn nom_period<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, (Zoned, Zoned)> {
+    separated_pair(nom_day_hour(timestamp), char('/'), nom_day_hour(timestamp)).parse(input)
+}
+
+/// `FMddhhmm`
+fn nom_from_time<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, Zoned> {
+    preceded(tag("FM"), |i| nom_day_time(i, timestamp)).parse(input)
+}
+
+fn nom_probability(input: &str) -> IResult<&str, u32> {
+    preceded(
+        tag("PROB"),
+        alt((value(30, tag("30")), value(40, tag("40")))),
+    )
+    .parse(input)
+}
+
+/// Change indicator with the probability, if any, and the period of the
+/// change. The conditions are filled in by [`nom_taf_change`].
+fn nom_change_header<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, TafChange> {
+    let change = |change, probability, from, until| TafChange {
+        change,
+        probability,
+        from,
+        until,
+        conditions: ForecastConditions::default(),
+        icing: Vec::new(),
+        turbulence: Vec::new(),
+    };
+    alt((
+        map(
+            |i| nom_from_time(i, timestamp),
+            |from| change(TafChangeType::From, None, from, None),
+        ),
+        map(
+            (
+                alt((
+                    map(
+                        (nom_probability, opt(preceded(char(' '), tag("TEMPO")))),
+                        |(probability, tempo)| match tempo {
+                            Some(_) => (TafChangeType::Temporary, Some(probability)),
+                            None => (TafChangeType::Probable, Some(probability)),
+                        },
+                    ),
+                    value((TafChangeType::Becoming, None), tag("BECMG")),
+                    value((TafChangeType::Temporary, None), tag("TEMPO")),
+                )),
+                preceded(char(' '), |i| nom_period(i, timestamp)),
+            ),
+            |((change_type, probability), (from, until))| {
+                change(change_type, probability, from, Some(until))
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+/// Icing and turbulence layers following the conditions.
+fn nom_layers(input: &str) -> IResult<&str, (Vec<IcingLayer>, Vec<TurbulenceLayer>)> {
+    many0(preceded(char(' '), nom_icing_or_turbulence))
+        .map(|layers| {
+            let mut icing = Vec::new();
+            let mut turbulence = Vec::new();
+            for layer in layers {
+                match layer {
+                    Layer::Icing(layer) => icing.push(layer),
+                    Layer::Turbulence(layer) => turbulence.push(layer),
+                }
+            }
+            (icing, turbulence)
+        })
+        .parse(input)
+}
+
+fn nom_temperatures<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, Vec<ForecastTemperature>> {
+    many0(preceded(char(' '), |i| {
+        nom_forecast_temperature(i, timestamp)
+    }))
+    .parse(input)
+}
+
+fn nom_taf_change<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, TafChange> {
+    verify(
+        (
+            |i| nom_change_header(i, timestamp),
+            nom_forecast_conditions,
+            nom_layers,
+        ),
+        |(_, conditions, (icing, turbulence))| {
+            !conditions.is_empty() || !icing.is_empty() || !turbulence.is_empty()
+        },
+    )
+    .map(|(change, conditions, (icing, turbulence))| TafChange {
+        conditions,
+        icing,
+        turbulence,
+        ..change
+    })
+    .parse(input)
+}
+
+/// Parses a TAF, with or without the leading `TAF`, issued at most an hour
+/// from now. See [`nom_parse_taf_at`].
+pub fn nom_parse_taf(input: &str) -> IResult<&str, Taf> {
+    nom_parse_taf_at(input, &Timestamp::new(reference_now()))
+}
+
+/// Parses a TAF issued before `reference`, e.g. the time of the bulletin it
+/// came in. The validity period and change groups are resolved against the
+/// issue time.
+pub fn nom_parse_taf_at<'a>(input: &'a str, reference: &Timestamp) -> IResult<&'a str, Taf> {
+    let (rest, _) = opt(terminated(tag("TAF"), char(' '))).parse(input)?;
+    let (rest, modifier) = opt(terminated(nom_report_modifier, char(' '))).parse(rest)?;
+    let (rest, (icao, issued)) = (
+        nom_icao,
+        preceded(char(' '), |i| nom_metar_timestamp_at(i, reference)),
+    )
+        .parse(rest)?;
+    let (rest, (valid_from, valid_until)) =
+        preceded(char(' '), |i| nom_period(i, &issued)).parse(rest)?;
+    let (rest, conditions) = nom_forecast_conditions(rest)?;
+    let (rest, (icing, turbulence)) = nom_layers(rest)?;
+    let (rest, mut temperatures) = nom_temperatures(rest, &issued)?;
+    let (rest, changes) = many0(preceded(char(' '), |i| nom_taf_change(i, &issued))).parse(rest)?;
+    // Some offices put the temperature groups after the change groups.
+    let (rest, trailing) = nom_temperatures(rest, &issued)?;
+    temperatures.extend(trailing);
+    Ok((
+        rest,
+        Taf {
+            raw: input[..input.len() - rest.len()].to_string(),
+            modifier,
+            icao: icao.to_string(),
+            issued,
+            valid_from,
+            valid_until,
+            conditions,
+            icing,
+            turbulence,
+            temperatures,
+            changes,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        obscuration::{MetersVisibility, Visibility},
+        optional_data::OptionalData::Data,
+        units::timestamp::test_time,
+        weather::WeatherPhenomenon,
+    };
+
+    #[test]
+    fn test_period_across_month_end() {
+        let (_, (from, until)) =
+            nom_period("3018/0124", &Timestamp::new(test_time(2025, 6, 30, 17, 0))).unwrap();
+        assert_eq!(from, test_time(2025, 6, 30, 18, 0));
+        assert_eq!(until, test_time(2025, 7, 2, 0, 0));
+    }
+
+    #[test]
+    fn test_change_groups() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 30, 17, 0));
+        let (rest, change) = nom_taf_change("FM302200 24010KT 9999 SCT030", &timestamp).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(change.change, TafChangeType::From);
+        assert_eq!(change.from, test_time(2025, 6, 30, 22, 0));
+        assert_eq!(change.until, None);
+        assert!(nom_taf_change("FM302430 24010KT", &timestamp).is_err());
+
+        let (_, change) = nom_taf_change("PROB30 TEMPO 0103/0106 0800 FG", &timestamp).unwrap();
+        assert_eq!(change.change, TafChangeType::Temporary);
+        assert_eq!(change.probability, Some(30));
+        assert_eq!(change.until, Some(test_time(2025, 7, 1, 6, 0)));
+        assert_eq!(
+            change.conditions.weather[0].phenomena,
+            vec![WeatherPhenomenon::Fog]
+        );
+
+        let (_, change) = nom_taf_change("PROB40 0103/0106 BKN005", &timestamp).unwrap();
+        assert_eq!(change.change, TafChangeType::Probable);
+    }
+
+    #[test]
+    fn test_parse_taf() {
+        let input = "TAF AMD ENGM 301700Z 3018/0124 20010KT 9999 FEW030 BECMG 3020/3022 VRB03KT TEMPO 0103/0106 4000 BR BKN008";
+        let (rest, taf) =
+            nom_parse_taf_at(input, &Timestamp::new(test_time(2025, 6, 30, 17, 10))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(taf.modifier, Some(ReportModifier::Amended));
+        assert_eq!(taf.icao, "ENGM");
+        assert_eq!(
+            taf.conditions.visibility,
+            Some(Visibility::Meters(MetersVisibility::new(Data(9999))))
+        );
+        assert_eq!(
+            taf.changes
+                .iter()
+                .map(|change| change.change)
+                .collect::<Vec<_>>(),
+            vec![TafChangeType::Becoming, TafChangeType::Temporary]
+        );
+    }
+
+    #[test]
+    fn test_raw_is_consumed_input() {
+        let input = "TAF ENGM 301700Z 3018/0124 20010KT 9999 FEW030=\nTAF ENBR";
+        let (rest, taf) =
+            nom_parse_taf_at(input, &Timestamp::new(test_time(2025, 6, 30, 17, 10))).unwrap();
+        assert_eq!(rest, "=\nTAF ENBR");
+        assert_eq!(taf.raw, "TAF ENGM 301700Z 3018/0124 20010KT 9999 FEW030");
+    }
+
+    #[test]
+    fn test_layer_only_changes() {
+        let input = "TAF KXYZ 301700Z 3018/0124 20010KT 9999 BKN030 TEMPO 0100/0104 540104 BECMG 0106/0108 25010KT 620304";
+        let (rest, taf) =
+            nom_parse_taf_at(input, &Timestamp::new(test_time(2025, 6, 30, 17, 10))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(taf.changes.len(), 2);
+        assert!(taf.changes[0].conditions.is_empty());
+        assert_eq!(taf.changes[0].turbulence[0].base, 1000);
+        assert_eq!(taf.changes[1].conditions.visibility, None);
+        assert_eq!(taf.changes[1].icing[0].base, 3000);
+    }
+
+    #[test]
+    fn test_temperatures_and_layers() {
+        let input = "TAF KXYZ 301700Z 3018/0124 20010KT 9999 BKN030 620304 530005 TX25/3020Z TNM01/0105Z TEMPO 0103/0106 BKN015 540104";
+        let (rest, taf) =
+            nom_parse_taf_at(input, &Timestamp::new(test_time(2025, 6, 30, 17, 10))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(taf.icing.len(), 1);
+        assert_eq!(taf.icing[0].base, 3000);
+        assert_eq!(taf.turbulence[0].top, 5000);
+        assert_eq!(taf.temperatures.len(), 2);
+        assert_eq!(taf.temperatures[1].temperature, -1);
+        assert_eq!(taf.changes[0].turbulence[0].base, 1000);
+        assert!(taf.changes[0].conditions.clouds.is_some());
+
+        let input = "TAF ENGM 301700Z 3018/0124 20010KT CAVOK BECMG 3020/3022 VRB03KT TX25/3014Z TN14/0105Z";
+        let (rest, taf) =
+            nom_parse_taf_at(input, &Timestamp::new(test_time(2025, 6, 30, 17, 10))).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(taf.changes.len(), 1);
+        assert_eq!(taf.temperatures.len(), 2);
+    }
+}
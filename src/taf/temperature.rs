@@ -0,0 +1,81 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{map_opt, value, verify},
+    sequence::{separated_pair, terminated},
+};
+
+use crate::{
+    temprature::nom_maybe_negative_temp,
+    units::timestamp::{Timestamp, parse_double_digit},
+};
+
+/// Forecast maximum or minimum temperature, e.g. `TX18/2914Z` or
+/// `TNM02/3004Z`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastTemperature {
+    pub kind: TemperatureKind,
+    /// Degrees Celsius.
+    pub temperature: i32,
+    pub time: Zoned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureKind {
+    /// `TX`
+    Maximum,
+    /// `TN`
+    Minimum,
+}
+
+pub(crate) fn nom_forecast_temperature<'a>(
+    input: &'a str,
+    timestamp: &Timestamp,
+) -> IResult<&'a str, ForecastTemperature> {
+    (
+        alt((
+            value(TemperatureKind::Maximum, tag("TX")),
+            value(TemperatureKind::Minimum, tag("TN")),
+        )),
+        separated_pair(
+            nom_maybe_negative_temp,
+            char('/'),
+            map_opt(
+                terminated(
+                    verify((parse_double_digit, parse_double_digit), |(_, hour)| {
+                        (0..=24).contains(hour)
+                    }),
+                    char('Z'),
+                ),
+                |(day, hour)| timestamp.nearest_day_time(day, hour, 0),
+            ),
+        ),
+    )
+        .map(|(kind, (temperature, time))| ForecastTemperature {
+            kind,
+            temperature,
+            time,
+        })
+        .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::timestamp::test_time;
+
+    #[test]
+    fn test_forecast_temperature() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 29, 11, 0));
+        let (_, maximum) = nom_forecast_temperature("TX18/2914Z", &timestamp).unwrap();
+        assert_eq!(maximum.kind, TemperatureKind::Maximum);
+        assert_eq!(maximum.temperature, 18);
+        assert_eq!(maximum.time, test_time(2025, 6, 29, 14, 0));
+        let (_, minimum) = nom_forecast_temperature("TNM02/3004Z", &timestamp).unwrap();
+        assert_eq!(minimum.kind, TemperatureKind::Minimum);
+        assert_eq!(minimum.temperature, -2);
+    }
+}
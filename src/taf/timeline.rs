@@ -0,0 +1,218 @@
+use jiff::Zoned;
+
+use crate::{
+    obscuration::{DescribedObscuration, MetersVisibility, Obscuration, SkyCondition, Visibility},
+    optional_data::OptionalData::Data,
+    taf::{Taf, TafChangeType},
+    trend::ForecastConditions,
+    weather::PresentWeather,
+    wind::Wind,
+};
+
+/// Forecast conditions at a point in time, with every group filled in from
+/// the change groups in effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForecastSnapshot {
+    pub wind: Option<Wind>,
+    /// `None` if the forecast gives clouds but no visibility.
+    pub obscuration: Option<Obscuration>,
+    pub weather: Vec<PresentWeather>,
+}
+
+/// Conditions that may occur instead of the prevailing ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternate {
+    /// `Temporary` or `Probable` for `TEMPO` and `PROB` groups, `Becoming`
+    /// while a `BECMG` change is still in progress.
+    pub change: TafChangeType,
+    pub probability: Option<u32>,
+    pub conditions: ForecastSnapshot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TafConditions {
+    pub prevailing: ForecastSnapshot,
+    pub alternates: Vec<Alternate>,
+}
+
+/// Drops `CAVOK` in favour of the visibility it implies, 10 km or more, so
+/// that a change to the weather or clouds keeps the prevailing visibility.
+fn leave_cavok(conditions: &mut ForecastConditions) {
+    if std::mem::take(&mut conditions.cavok) {
+        conditions.visibility = Some(Visibility::Meters(MetersVisibility::new(Data(9999))));
+    }
+}
+
+/// Replaces the groups given in `change`, keeping the rest.
+fn apply(conditions: &mut ForecastConditions, change: &ForecastConditions) {
+    if let Some(wind) = &change.wind {
+        conditions.wind = Some(wind.clone());
+    }
+    if change.cavok {
+        conditions.cavok = true;
+        conditions.visibility = None;
+        conditions.weather.clear();
+        conditions.clouds = None;
+    }
+    if let Some(visibility) = &change.visibility {
+        conditions.cavok = false;
+        conditions.visibility = Some(visibility.clone());
+    }
+    if change.no_significant_weather {
+        conditions.weather.clear();
+    } else if !change.weather.is_empty() {
+        leave_cavok(conditions);
+        conditions.weather = change.weather.clone();
+    }
+    if let Some(clouds) = &change.clouds {
+        leave_cavok(conditions);
+        conditions.clouds = Some(clouds.clone());
+    }
+}
+
+impl From<ForecastConditions> for ForecastSnapshot {
+    fn from(conditions: ForecastConditions) -> Self {
+        let obscuration = if conditions.cavok {
+            Some(Obscuration::Cavok)
+        } else {
+            conditions.visibility.map(|visibility| {
+                Obscuration::Described(DescribedObscuration {
+                    visibility,
+                    rvr: Vec::new(),
+                    clouds: conditions
+                        .clouds
+                        .unwrap_or(SkyCondition::Clouds(Vec::new())),
+                })
+            })
+        };
+        ForecastSnapshot {
+            wind: conditions.wind,
+            obscuration,
+            weather: conditions.weather,
+        }
+    }
+}
+
+impl Taf {
+    /// What the forecast says at `time`, or `None` outside the validity
+    /// period.
+    ///
+    /// `FM` groups replace the conditions and completed `BECMG` changes are
+    /// applied on top. A `BECMG` change in progress, and `TEMPO` and `PROB`
+    /// groups covering `time`, are returned as alternates applied to the
+    /// prevailing conditions.
+    pub fn conditions_at(&self, time: &Zoned) -> Option<TafConditions> {
+        if *time < self.valid_from || *time >= self.valid_until {
+            return None;
+        }
+        let mut prevailing = self.conditions.clone();
+        let mut active = Vec::new();
+        for change in self.changes.iter().filter(|change| change.from <= *time) {
+            let ended = change.until.as_ref().is_none_or(|until| until <= time);
+            match change.change {
+                TafChangeType::From => prevailing = change.conditions.clone(),
+                TafChangeType::Becoming if ended => apply(&mut prevailing, &change.conditions),
+                _ if !ended => active.push(change),
+                _ => {}
+            }
+        }
+        let alternates = active
+            .into_iter()
+            .map(|change| {
+                let mut conditions = prevailing.clone();
+                apply(&mut conditions, &change.conditions);
+                Alternate {
+                    change: change.change,
+                    probability: change.probability,
+                    conditions: conditions.into(),
+                }
+            })
+            .collect();
+        Some(TafConditions {
+            prevailing: prevailing.into(),
+            alternates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        taf::nom_parse_taf_at,
+        units::{
+            timestamp::{Timestamp, test_time},
+            track::Track,
+        },
+        wind::WindDirection,
+    };
+
+    const TAF: &str = "TAF ENGM 301700Z 3018/0124 20010KT 9999 FEW030 BECMG 3020/3022 VRB03KT FM010600 25015KT CAVOK TEMPO 0110/0114 4000 SHRA BKN012";
+
+    fn parse() -> Taf {
+        let reference = Timestamp::new(test_time(2025, 6, 30, 17, 10));
+        let (_, taf) = nom_parse_taf_at(TAF, &reference).unwrap();
+        taf
+    }
+
+    #[test]
+    fn test_outside_validity() {
+        assert_eq!(parse().conditions_at(&test_time(2025, 6, 30, 17, 0)), None);
+        assert_eq!(parse().conditions_at(&test_time(2025, 7, 2, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_becoming() {
+        let taf = parse();
+        let during = taf.conditions_at(&test_time(2025, 6, 30, 21, 0)).unwrap();
+        assert_eq!(
+            during.prevailing.wind.unwrap().dir,
+            WindDirection::Heading(Track(Data(200)))
+        );
+        assert_eq!(during.alternates[0].change, TafChangeType::Becoming);
+
+        let after = taf.conditions_at(&test_time(2025, 6, 30, 23, 0)).unwrap();
+        assert_eq!(after.prevailing.wind.unwrap().dir, WindDirection::Variable);
+        assert!(after.alternates.is_empty());
+        let Some(Obscuration::Described(obscuration)) = after.prevailing.obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(
+            obscuration.visibility,
+            Visibility::Meters(MetersVisibility::new(Data(9999)))
+        );
+    }
+
+    #[test]
+    fn test_from_and_tempo() {
+        let conditions = parse()
+            .conditions_at(&test_time(2025, 7, 1, 12, 30))
+            .unwrap();
+        assert_eq!(conditions.prevailing.obscuration, Some(Obscuration::Cavok));
+        assert_eq!(conditions.alternates.len(), 1);
+        let tempo = &conditions.alternates[0];
+        assert_eq!(tempo.change, TafChangeType::Temporary);
+        assert_eq!(tempo.conditions.wind, conditions.prevailing.wind);
+        let Some(Obscuration::Described(obscuration)) = &tempo.conditions.obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(obscuration.clouds.layers().len(), 1);
+        assert_eq!(tempo.conditions.weather.len(), 1);
+    }
+
+    #[test]
+    fn test_clouds_after_cavok_keep_visibility() {
+        let input = "TAF ENGM 291700Z 2918/3024 20010KT CAVOK BECMG 2920/2922 BKN020";
+        let reference = Timestamp::new(test_time(2025, 6, 29, 17, 10));
+        let (_, taf) = nom_parse_taf_at(input, &reference).unwrap();
+        let conditions = taf.conditions_at(&test_time(2025, 6, 30, 0, 0)).unwrap();
+        let Some(Obscuration::Described(obscuration)) = conditions.prevailing.obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(
+            obscuration.visibility,
+            Visibility::Meters(MetersVisibility::new(Data(9999)))
+        );
+        assert_eq!(obscuration.clouds.layers().len(), 1);
+    }
+}
@@ -23,7 +23,7 @@ pub(crate) fn nom_temprature(input: &str) -> IResult<&str, Temprature> {
     .parse(input)
 }
 
-fn nom_maybe_negative_temp(input: &str) -> IResult<&str, i32> {
+pub(crate) fn nom_maybe_negative_temp(input: &str) -> IResult<&str, i32> {
     (opt(char('M')), i32)
         .map(|(sign, temp)| if sign.is_some() { -temp } else { temp })
         .parse(input)
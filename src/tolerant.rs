@@ -0,0 +1,476 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_till},
+    character::complete::char,
+    combinator::{eof, fail, map, opt, peek, value},
+    sequence::{preceded, separated_pair, terminated},
+};
+use thiserror::Error;
+
+use crate::{
+    colour::{ColourState, nom_colour_state},
+    metar::{
+        Metar, ReportModifier, ReportType, UnparsedGroup, nom_icao, nom_report_modifier,
+        nom_report_type,
+    },
+    obscuration::{
+        Cloud, DescribedObscuration, Obscuration, Rvr, SkyCondition, Visibility, nom_rvr,
+        nom_sky_condition, nom_visibility,
+    },
+    pressure::{Pressure, nom_pressure},
+    remarks::{Remarks, parse_remarks},
+    runway::{RunwayState, WindShear, nom_runway_state, nom_wind_shear},
+    sea::{SeaState, nom_sea_state},
+    temprature::{Temprature, nom_temprature},
+    trend::{Trend, nom_trend},
+    units::{
+        timestamp::{Timestamp, nom_metar_timestamp},
+        track::{Track, nom_track},
+    },
+    weather::{PresentWeather, RecentWeather, nom_present_weather, nom_recent_weather},
+    wind::{Wind, nom_wind},
+};
+
+/// Mandatory groups that were not found by [`parse_metar_tolerant`].
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum TolerantParseError {
+    #[error("Parse error: Missing ICAO location indicator")]
+    MissingIcao,
+    #[error("Parse error: Missing observation time")]
+    MissingTimestamp,
+    #[error("Parse error: Missing wind")]
+    MissingWind,
+    #[error("Parse error: Missing visibility")]
+    MissingVisibility,
+    #[error("Parse error: Missing temperature")]
+    MissingTemperature,
+    #[error("Parse error: Missing pressure")]
+    MissingPressure,
+}
+
+#[derive(Clone)]
+enum Group {
+    ReportType(ReportType),
+    Modifier(ReportModifier),
+    Auto,
+    Nosig,
+    Cavok,
+    NoDirectionalVariation,
+    Icao(String),
+    Timestamp(Timestamp),
+    Wind(Wind),
+    WindVariation((Track, Track)),
+    Visibility(Visibility),
+    Rvr(Rvr),
+    Weather(PresentWeather),
+    SkyCondition(SkyCondition),
+    Temprature(Temprature),
+    Pressure(Pressure),
+    RecentWeather(RecentWeather),
+    WindShear(WindShear),
+    SeaState(SeaState),
+    RunwayState(RunwayState),
+    ColourState(ColourState),
+    Trend(Trend),
+    Remarks(Remarks),
+}
+
+/// Fields found so far. Singular groups are only accepted once, a repeated
+/// group is kept as unparsed. `CAVOK` excludes visibility, RVR and cloud
+/// groups, whichever comes second is kept as unparsed.
+#[derive(Default)]
+struct Groups {
+    /// A group after the header has been accepted, so an ICAO code is no
+    /// longer expected.
+    body: bool,
+    report_type: Option<ReportType>,
+    correction: Option<ReportModifier>,
+    icao: Option<String>,
+    timestamp: Option<Timestamp>,
+    auto: bool,
+    wind: Option<Wind>,
+    cavok: bool,
+    visibility: Option<Visibility>,
+    rvr: Vec<Rvr>,
+    weather: Vec<PresentWeather>,
+    sky_condition: Option<SkyCondition>,
+    clouds: Vec<Cloud>,
+    temprature: Option<Temprature>,
+    pressure: Option<Pressure>,
+    recent_weather: Vec<RecentWeather>,
+    wind_shear: Option<WindShear>,
+    sea_state: Option<SeaState>,
+    runway_state: Vec<RunwayState>,
+    colour_state: Option<ColourState>,
+    nosig: bool,
+    trends: Vec<Trend>,
+    remarks: Option<Remarks>,
+}
+
+fn set<T>(field: &mut Option<T>, value: T) -> bool {
+    if field.is_some() {
+        return false;
+    }
+    *field = Some(value);
+    true
+}
+
+fn set_flag(flag: &mut bool) -> bool {
+    !std::mem::replace(flag, true)
+}
+
+impl Groups {
+    fn accept(&mut self, group: Group) -> bool {
+        let header = matches!(
+            group,
+            Group::ReportType(_)
+                | Group::Modifier(_)
+                | Group::Auto
+                | Group::Icao(_)
+                | Group::Timestamp(_)
+        );
+        let accepted = match group {
+            Group::ReportType(report_type) => set(&mut self.report_type, report_type),
+            Group::Modifier(modifier) => set(&mut self.correction, modifier),
+            Group::Auto => set_flag(&mut self.auto),
+            Group::Nosig => set_flag(&mut self.nosig),
+            Group::Cavok => {
+                self.visibility.is_none()
+                    && self.rvr.is_empty()
+                    && self.sky_condition.is_none()
+                    && self.clouds.is_empty()
+                    && set_flag(&mut self.cavok)
+            }
+            Group::Visibility(_) | Group::Rvr(_) | Group::SkyCondition(_) if self.cavok => false,
+            Group::NoDirectionalVariation => match &mut self.visibility {
+                Some(Visibility::Meters(visibility)) => {
+                    set_flag(&mut visibility.no_directional_variation)
+                }
+                _ => false,
+            },
+            Group::Icao(icao) => !self.body && set(&mut self.icao, icao),
+            Group::Timestamp(timestamp) => set(&mut self.timestamp, timestamp),
+            Group::Wind(wind) => set(&mut self.wind, wind),
+            Group::WindVariation(varying) => match &mut self.wind {
+                Some(wind) => set(&mut wind.varying, varying),
+                None => false,
+            },
+            Group::Visibility(visibility) => set(&mut self.visibility, visibility),
+            Group::Rvr(rvr) => {
+                self.rvr.push(rvr);
+                true
+            }
+            Group::Weather(weather) => {
+                self.weather.push(weather);
+                true
+            }
+            // The other sky conditions, e.g. `NCD` or `VV`, exclude cloud
+            // layers.
+            Group::SkyCondition(SkyCondition::Clouds(clouds)) if self.sky_condition.is_none() => {
+                self.clouds.extend(clouds);
+                true
+            }
+            Group::SkyCondition(SkyCondition::Clouds(_)) => false,
+            Group::SkyCondition(sky_condition) => {
+                self.clouds.is_empty() && set(&mut self.sky_condition, sky_condition)
+            }
+            Group::Temprature(temprature) => set(&mut self.temprature, temprature),
+            Group::Pressure(pressure) => set(&mut self.pressure, pressure),
+            Group::RecentWeather(weather) => {
+                self.recent_weather.push(weather);
+                true
+            }
+            Group::WindShear(wind_shear) => set(&mut self.wind_shear, wind_shear),
+            Group::SeaState(sea_state) => set(&mut self.sea_state, sea_state),
+            Group::RunwayState(state) => {
+                self.runway_state.push(state);
+                true
+            }
+            Group::ColourState(colour_state) => set(&mut self.colour_state, colour_state),
+            Group::Trend(trend) => {
+                self.trends.push(trend);
+                true
+            }
+            Group::Remarks(remarks) => set(&mut self.remarks, remarks),
+        };
+        self.body |= accepted && !header;
+        accepted
+    }
+
+    fn into_metar(
+        self,
+        input: &str,
+        unparsed: Vec<UnparsedGroup>,
+    ) -> Result<Metar, TolerantParseError> {
+        let icao = self.icao.ok_or(TolerantParseError::MissingIcao)?;
+        let timestamp = self.timestamp.ok_or(TolerantParseError::MissingTimestamp)?;
+        let wind = self.wind.ok_or(TolerantParseError::MissingWind)?;
+        let obscuration = if self.cavok {
+            Obscuration::Cavok
+        } else {
+            Obscuration::Described(DescribedObscuration {
+                visibility: self
+                    .visibility
+                    .ok_or(TolerantParseError::MissingVisibility)?,
+                rvr: self.rvr,
+                clouds: self
+                    .sky_condition
+                    .unwrap_or(SkyCondition::Clouds(self.clouds)),
+            })
+        };
+        let temprature = self
+            .temprature
+            .ok_or(TolerantParseError::MissingTemperature)?;
+        let pressure = self.pressure.ok_or(TolerantParseError::MissingPressure)?;
+        Ok(Metar {
+            raw: input.to_string(),
+            report_type: self.report_type.unwrap_or_default(),
+            correction: self.correction,
+            icao,
+            timestamp,
+            auto: self.auto,
+            wind,
+            obscuration,
+            weather: self.weather,
+            temprature,
+            pressure,
+            recent_weather: self.recent_weather,
+            wind_shear: self.wind_shear,
+            sea_state: self.sea_state,
+            runway_state: self.runway_state,
+            colour_state: self.colour_state,
+            nosig: self.nosig,
+            trends: self.trends,
+            remarks: self.remarks,
+            unparsed,
+        })
+    }
+}
+
+fn nom_group_end(input: &str) -> IResult<&str, &str> {
+    peek(alt((tag(" "), eof))).parse(input)
+}
+
+/// Groups that need the report time, only tried once it is known.
+fn nom_timed_group<'a>(input: &'a str, timestamp: Option<&Timestamp>) -> IResult<&'a str, Group> {
+    let Some(timestamp) = timestamp else {
+        return fail().parse(input);
+    };
+    alt((
+        map(
+            preceded(
+                tag("RMK"),
+                opt(preceded(char(' '), take_till(|c| c == '\n'))),
+            ),
+            |remark| Group::Remarks(parse_remarks(remark.unwrap_or_default(), timestamp)),
+        ),
+        terminated(
+            map(|i| nom_trend(i, timestamp), Group::Trend),
+            nom_group_end,
+        ),
+    ))
+    .parse(input)
+}
+
+/// Keywords and the header come first so that e.g. `AUTO` is never read as
+/// an ICAO code, which is only tried as a last resort and only accepted in
+/// the header.
+fn nom_group<'a>(input: &'a str, timestamp: Option<&Timestamp>) -> IResult<&'a str, Group> {
+    alt((
+        |i| nom_timed_group(i, timestamp),
+        terminated(
+            alt((
+                map(nom_report_type, Group::ReportType),
+                map(nom_report_modifier, Group::Modifier),
+                value(Group::Auto, tag("AUTO")),
+                value(Group::Nosig, tag("NOSIG")),
+                value(Group::Cavok, tag("CAVOK")),
+                value(Group::NoDirectionalVariation, tag("NDV")),
+            )),
+            nom_group_end,
+        ),
+        terminated(map(nom_metar_timestamp, Group::Timestamp), nom_group_end),
+        terminated(map(nom_wind, Group::Wind), nom_group_end),
+        terminated(
+            map(
+                separated_pair(nom_track, char('V'), nom_track),
+                Group::WindVariation,
+            ),
+            nom_group_end,
+        ),
+        terminated(map(nom_visibility, Group::Visibility), nom_group_end),
+        terminated(map(nom_rvr, Group::Rvr), nom_group_end),
+        terminated(map(nom_temprature, Group::Temprature), nom_group_end),
+        terminated(map(nom_pressure, Group::Pressure), nom_group_end),
+        terminated(map(nom_recent_weather, Group::RecentWeather), nom_group_end),
+        terminated(map(nom_wind_shear, Group::WindShear), nom_group_end),
+        terminated(map(nom_sea_state, Group::SeaState), nom_group_end),
+        terminated(map(nom_runway_state, Group::RunwayState), nom_group_end),
+        terminated(map(nom_colour_state, Group::ColourState), nom_group_end),
+        terminated(map(nom_sky_condition, Group::SkyCondition), nom_group_end),
+        terminated(map(nom_present_weather, Group::Weather), nom_group_end),
+        terminated(
+            map(nom_icao, |icao| Group::Icao(icao.to_string())),
+            nom_group_end,
+        ),
+    ))
+    .parse(input)
+}
+
+/// Parses a report without relying on the order of the groups.
+///
+/// Every whitespace separated group is classified on its own. Groups that are
+/// not recognised, or repeat a group that may only appear once, are collected
+/// in [`Metar::unparsed`] with their byte range. Parsing only fails if one of
+/// the groups needed to build a [`Metar`] is missing.
+pub fn parse_metar_tolerant(input: &str) -> Result<Metar, TolerantParseError> {
+    let mut groups = Groups::default();
+    let mut unparsed = Vec::new();
+    let mut rest = input.trim_start();
+    while !rest.is_empty() {
+        let start = input.len() - rest.len();
+        let accepted = match nom_group(rest, groups.timestamp.as_ref()) {
+            Ok((next, group)) => groups.accept(group).then_some(next),
+            Err(_) => None,
+        };
+        rest = match accepted {
+            Some(next) => next,
+            None => {
+                let (text, next) =
+                    rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len()));
+                unparsed.push(UnparsedGroup {
+                    text: text.to_string(),
+                    span: start..start + text.len(),
+                });
+                next
+            }
+        };
+        rest = rest.trim_start();
+    }
+    groups.into_metar(input, unparsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metar::nom_parse_metar, obscuration::MetersVisibility};
+
+    #[test]
+    fn test_matches_strict_parser() {
+        let input = "ENBR 291820Z 29008KT 250V320 9999 SCT015TCU BKN022 13/10 Q1019 NOSIG RMK WIND 1200FT 30015KT";
+        let (_, strict) = nom_parse_metar(input).unwrap();
+        let tolerant = parse_metar_tolerant(input).unwrap();
+        assert_eq!(tolerant.wind, strict.wind);
+        assert_eq!(tolerant.obscuration, strict.obscuration);
+        assert_eq!(tolerant.remarks, strict.remarks);
+        assert!(tolerant.nosig);
+        assert!(tolerant.unparsed.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_header() {
+        let input = "METAR AUTO ENSS 291820Z COR 09013KT 9999 NDV OVC006/// 07/05 Q1006";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert!(metar.auto);
+        assert_eq!(metar.icao, "ENSS");
+        assert_eq!(metar.correction, Some(ReportModifier::Corrected));
+        let Obscuration::Described(obscuration) = metar.obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(
+            obscuration.visibility,
+            Visibility::Meters(MetersVisibility {
+                no_directional_variation: true,
+                ..MetersVisibility::new(crate::optional_data::OptionalData::Data(9999))
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_groups() {
+        let input = "ENGM 291820Z 20005KT 9999 FOO FEW030 14/09 14/08 Q1000";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert_eq!(
+            metar.unparsed,
+            vec![
+                UnparsedGroup {
+                    text: "FOO".to_string(),
+                    span: 26..29,
+                },
+                UnparsedGroup {
+                    text: "14/08".to_string(),
+                    span: 43..48,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_junk_timestamps() {
+        let input = "ENGM 999999Z 291820Z 20005KT 9999 FEW030 14/09 Q1000 002460Z";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert_eq!(
+            metar
+                .unparsed
+                .iter()
+                .map(|group| group.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["999999Z", "002460Z"]
+        );
+        assert_eq!(metar.timestamp.time().day(), 29);
+    }
+
+    #[test]
+    fn test_conflicting_sky_condition() {
+        let input = "ENGM 291820Z 20005KT 9999 FEW030 NCD BKN050 14/09 Q1000";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert_eq!(metar.unparsed[0].text, "NCD");
+        let Obscuration::Described(obscuration) = metar.obscuration else {
+            panic!("expected described obscuration");
+        };
+        assert_eq!(obscuration.clouds.layers().len(), 2);
+    }
+
+    #[test]
+    fn test_conflicting_cavok() {
+        let input = "ENGM 291820Z 20005KT CAVOK 9999 R01/1200 FEW030 14/09 Q1000";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert_eq!(metar.obscuration, Obscuration::Cavok);
+        assert_eq!(
+            metar
+                .unparsed
+                .iter()
+                .map(|group| (group.text.as_str(), group.span.clone()))
+                .collect::<Vec<_>>(),
+            vec![("9999", 27..31), ("R01/1200", 32..40), ("FEW030", 41..47)]
+        );
+
+        let input = "ENGM 291820Z 20005KT 9999 CAVOK 14/09 Q1000";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert!(matches!(metar.obscuration, Obscuration::Described(_)));
+        assert_eq!(metar.unparsed[0].text, "CAVOK");
+    }
+
+    #[test]
+    fn test_icao_only_in_header() {
+        let input = "ENGM 291820Z 20005KT 9999 ABCD FEW030 14/09 Q1000";
+        let metar = parse_metar_tolerant(input).unwrap();
+        assert_eq!(metar.icao, "ENGM");
+        assert_eq!(metar.unparsed[0].text, "ABCD");
+
+        let input = "20005KT ENGM 291820Z 9999 FEW030 14/09 Q1000";
+        assert_eq!(
+            parse_metar_tolerant(input).unwrap_err(),
+            TolerantParseError::MissingIcao
+        );
+    }
+
+    #[test]
+    fn test_missing_mandatory_group() {
+        assert_eq!(
+            parse_metar_tolerant("ENGM 291820Z 20005KT 9999 FEW030 Q1000").unwrap_err(),
+            TolerantParseError::MissingTemperature
+        );
+    }
+}
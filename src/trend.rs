@@ -0,0 +1,237 @@
+use jiff::Zoned;
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::char,
+    combinator::{map, opt, value, verify},
+    multi::many0,
+    sequence::preceded,
+};
+
+use crate::{
+    colour::{ColourState, nom_colour_state},
+    obscuration::{SkyCondition, Visibility, nom_sky_condition, nom_visibility},
+    units::timestamp::{Timestamp, nom_hour_minute},
+    weather::{PresentWeather, nom_present_weather},
+    wind::{Wind, nom_wind},
+};
+
+/// A `BECMG` or `TEMPO` trend forecast, covering the two hours after the
+/// observation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    pub change: TrendChange,
+    pub from: Option<Zoned>,
+    pub until: Option<Zoned>,
+    pub at: Option<Zoned>,
+    pub conditions: ForecastConditions,
+    /// Forecast military colour state.
+    pub colour_state: Option<ColourState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendChange {
+    Becoming,
+    Temporary,
+}
+
+/// The forecast groups of a change group. Only the elements that are
+/// expected to change are reported, so everything is optional.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ForecastConditions {
+    pub wind: Option<Wind>,
+    pub visibility: Option<Visibility>,
+    pub cavok: bool,
+    pub weather: Vec<PresentWeather>,
+    pub no_significant_weather: bool,
+    pub clouds: Option<SkyCondition>,
+}
+
+impl ForecastConditions {
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == ForecastConditions::default()
+    }
+}
+
+fn nom_trend_change(input: &str) -> IResult<&str, TrendChange> {
+    alt((
+        value(TrendChange::Becoming, tag("BECMG")),
+        value(TrendChange::Temporary, tag("TEMPO")),
+    ))
+    .parse(input)
+}
+
+fn nom_trend_time<'a>(
+    indicator: &'static str,
+    timestamp: &Timestamp,
+) -> impl Parser<&'a str, Output = Zoned, Error = nom::error::Error<&'a str>> {
+    preceded((char(' '), tag(indicator)), nom_hour_minute)
+        .map(|(hour, minute)| timestamp.next_time_of_day(hour, minute))
+}
+
+pub(crate) fn nom_forecast_conditions(input: &str) -> IResult<&str, ForecastConditions> {
+    let (rest, wind) = opt(preceded(char(' '), nom_wind)).parse(input)?;
+    let (rest, (cavok, visibility)) = alt((
+        value((true, None), preceded(char(' '), tag("CAVOK"))),
+        map(opt(preceded(char(' '), nom_visibility)), |visibility| {
+            (false, visibility)
+        }),
+    ))
+    .parse(rest)?;
+    let (rest, (no_significant_weather, weather)) = alt((
+        value((true, Vec::new()), preceded(char(' '), tag("NSW"))),
+        map(many0(preceded(char(' '), nom_present_weather)), |weather| {
+            (false, weather)
+        }),
+    ))
+    .parse(rest)?;
+    let (rest, clouds) = opt(preceded(char(' '), nom_sky_condition)).parse(rest)?;
+    Ok((
+        rest,
+        ForecastConditions {
+            wind,
+            visibility,
+            cavok,
+            weather,
+            no_significant_weather,
+            clouds,
+        },
+    ))
+}
+
+/// Parses a single trend group, starting at the change indicator. Time groups
+/// are resolved against the time of the observation.
+pub(crate) fn nom_trend<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, Trend> {
+    let (rest, change) = nom_trend_change.parse(input)?;
+    let (rest, (from, until, at)) = (
+        opt(nom_trend_time("FM", timestamp)),
+        opt(nom_trend_time("TL", timestamp)),
+        opt(nom_trend_time("AT", timestamp)),
+    )
+        .parse(rest)?;
+    let (rest, (conditions, colour_state)) = verify(
+        (
+            nom_forecast_conditions,
+            opt(preceded(char(' '), nom_colour_state)),
+        ),
+        |(conditions, colour_state)| !conditions.is_empty() || colour_state.is_some(),
+    )
+    .parse(rest)?;
+    Ok((
+        rest,
+        Trend {
+            change,
+            from,
+            until,
+            at,
+            conditions,
+            colour_state,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        obscuration::{CloudCoverage, MetersVisibility},
+        optional_data::OptionalData::{self, Data},
+        units::timestamp::test_time,
+        weather::{WeatherDescriptor, WeatherIntensity, WeatherPhenomenon},
+    };
+
+    #[test]
+    fn test_becoming_wind() {
+        let (rest, trend) = nom_trend(
+            "BECMG 24010KT RMK",
+            &Timestamp::new(test_time(2025, 6, 29, 18, 20)),
+        )
+        .unwrap();
+        assert_eq!(rest, " RMK");
+        assert_eq!(trend.change, TrendChange::Becoming);
+        assert!(trend.conditions.wind.is_some());
+        assert_eq!(trend.conditions.visibility, None);
+        assert_eq!(trend.conditions.clouds, None);
+    }
+
+    #[test]
+    fn test_tempo_full() {
+        let (rest, trend) = nom_trend(
+            "TEMPO 26030G42KT 3000 SHRA BKN012CB",
+            &Timestamp::new(test_time(2025, 6, 29, 18, 20)),
+        )
+        .unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(trend.change, TrendChange::Temporary);
+        assert_eq!(
+            trend.conditions.visibility,
+            Some(Visibility::Meters(MetersVisibility::new(Data(3000))))
+        );
+        assert_eq!(
+            trend.conditions.weather,
+            vec![PresentWeather {
+                intensity: WeatherIntensity::Moderate,
+                descriptor: Some(WeatherDescriptor::Showers),
+                phenomena: vec![WeatherPhenomenon::Rain],
+            }]
+        );
+        let clouds = trend.conditions.clouds.unwrap();
+        assert_eq!(clouds.layers().len(), 1);
+        assert_eq!(
+            clouds.layers()[0].coverage,
+            OptionalData::Data(CloudCoverage::Broken)
+        );
+    }
+
+    #[test]
+    fn test_time_groups() {
+        let (_, trend) = nom_trend(
+            "BECMG FM1900 TL2000 CAVOK NSW",
+            &Timestamp::new(test_time(2025, 6, 29, 18, 20)),
+        )
+        .unwrap();
+        let expected_from = test_time(2025, 6, 29, 19, 0);
+        let expected_until = test_time(2025, 6, 29, 20, 0);
+        assert_eq!(trend.from, Some(expected_from));
+        assert_eq!(trend.until, Some(expected_until));
+        assert_eq!(trend.at, None);
+        assert!(trend.conditions.cavok);
+        assert!(trend.conditions.no_significant_weather);
+    }
+
+    #[test]
+    fn test_no_significant_cloud() {
+        let (_, trend) = nom_trend(
+            "BECMG 9999 NSW NSC",
+            &Timestamp::new(test_time(2025, 6, 29, 18, 20)),
+        )
+        .unwrap();
+        assert_eq!(
+            trend.conditions.clouds,
+            Some(SkyCondition::NoSignificantCloud)
+        );
+    }
+
+    #[test]
+    fn test_time_group_after_midnight() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 29, 23, 20));
+        let (_, trend) = nom_trend("TEMPO TL0100 4000 BR", &timestamp).unwrap();
+        let expected = test_time(2025, 6, 30, 1, 0);
+        assert_eq!(trend.until, Some(expected));
+    }
+
+    #[test]
+    fn test_hour_24_only_at_midnight() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 29, 18, 20));
+        let (_, trend) = nom_trend("BECMG TL2400 CAVOK", &timestamp).unwrap();
+        let expected = test_time(2025, 6, 30, 0, 0);
+        assert_eq!(trend.until, Some(expected));
+        assert!(nom_trend("BECMG TL2430 CAVOK", &timestamp).is_err());
+    }
+
+    #[test]
+    fn test_empty_trend() {
+        assert!(nom_trend("BECMG RMK", &Timestamp::new(test_time(2025, 6, 29, 18, 20))).is_err());
+    }
+}
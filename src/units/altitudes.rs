@@ -14,6 +14,14 @@ pub struct CloudHeight {
     pub height: i32,
 }
 
+impl CloudHeight {
+    /// Height above aerodrome level in feet. Reported heights are in units of
+    /// 100 ft.
+    pub fn feet(&self) -> i32 {
+        self.height * 100
+    }
+}
+
 impl Display for CloudHeight {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:03}FT", self.height)
@@ -0,0 +1,171 @@
+use std::fmt::{self, Display};
+
+use nom::{IResult, Parser, branch::alt, bytes::complete::tag, combinator::value};
+
+/// One of the eight compass octants, as used for directional visibility and
+/// the location of phenomena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompassOctant {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl CompassOctant {
+    /// True bearing of the centre of the octant, in degrees.
+    pub fn bearing(&self) -> u32 {
+        match self {
+            CompassOctant::North => 0,
+            CompassOctant::NorthEast => 45,
+            CompassOctant::East => 90,
+            CompassOctant::SouthEast => 135,
+            CompassOctant::South => 180,
+            CompassOctant::SouthWest => 225,
+            CompassOctant::West => 270,
+            CompassOctant::NorthWest => 315,
+        }
+    }
+}
+
+impl Display for CompassOctant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            CompassOctant::North => "N",
+            CompassOctant::NorthEast => "NE",
+            CompassOctant::East => "E",
+            CompassOctant::SouthEast => "SE",
+            CompassOctant::South => "S",
+            CompassOctant::SouthWest => "SW",
+            CompassOctant::West => "W",
+            CompassOctant::NorthWest => "NW",
+        };
+        write!(f, "{code}")
+    }
+}
+
+pub(crate) fn nom_compass_octant(input: &str) -> IResult<&str, CompassOctant> {
+    alt((
+        value(CompassOctant::NorthEast, tag("NE")),
+        value(CompassOctant::NorthWest, tag("NW")),
+        value(CompassOctant::SouthEast, tag("SE")),
+        value(CompassOctant::SouthWest, tag("SW")),
+        value(CompassOctant::North, tag("N")),
+        value(CompassOctant::East, tag("E")),
+        value(CompassOctant::South, tag("S")),
+        value(CompassOctant::West, tag("W")),
+    ))
+    .parse(input)
+}
+
+/// One of the sixteen points of the compass, as used for the movement of
+/// SIGMET phenomena.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompassPoint {
+    North,
+    NorthNorthEast,
+    NorthEast,
+    EastNorthEast,
+    East,
+    EastSouthEast,
+    SouthEast,
+    SouthSouthEast,
+    South,
+    SouthSouthWest,
+    SouthWest,
+    WestSouthWest,
+    West,
+    WestNorthWest,
+    NorthWest,
+    NorthNorthWest,
+}
+
+impl CompassPoint {
+    /// True bearing of the point, in degrees.
+    pub fn bearing(&self) -> f64 {
+        f64::from(*self as u8) * 22.5
+    }
+}
+
+impl Display for CompassPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            CompassPoint::North => "N",
+            CompassPoint::NorthNorthEast => "NNE",
+            CompassPoint::NorthEast => "NE",
+            CompassPoint::EastNorthEast => "ENE",
+            CompassPoint::East => "E",
+            CompassPoint::EastSouthEast => "ESE",
+            CompassPoint::SouthEast => "SE",
+            CompassPoint::SouthSouthEast => "SSE",
+            CompassPoint::South => "S",
+            CompassPoint::SouthSouthWest => "SSW",
+            CompassPoint::SouthWest => "SW",
+            CompassPoint::WestSouthWest => "WSW",
+            CompassPoint::West => "W",
+            CompassPoint::WestNorthWest => "WNW",
+            CompassPoint::NorthWest => "NW",
+            CompassPoint::NorthNorthWest => "NNW",
+        };
+        write!(f, "{code}")
+    }
+}
+
+/// Longer codes are tried first so that `NNE` is not read as `N`.
+pub(crate) fn nom_compass_point(input: &str) -> IResult<&str, CompassPoint> {
+    alt((
+        alt((
+            value(CompassPoint::NorthNorthEast, tag("NNE")),
+            value(CompassPoint::EastNorthEast, tag("ENE")),
+            value(CompassPoint::EastSouthEast, tag("ESE")),
+            value(CompassPoint::SouthSouthEast, tag("SSE")),
+            value(CompassPoint::SouthSouthWest, tag("SSW")),
+            value(CompassPoint::WestSouthWest, tag("WSW")),
+            value(CompassPoint::WestNorthWest, tag("WNW")),
+            value(CompassPoint::NorthNorthWest, tag("NNW")),
+        )),
+        alt((
+            value(CompassPoint::NorthEast, tag("NE")),
+            value(CompassPoint::SouthEast, tag("SE")),
+            value(CompassPoint::SouthWest, tag("SW")),
+            value(CompassPoint::NorthWest, tag("NW")),
+            value(CompassPoint::North, tag("N")),
+            value(CompassPoint::East, tag("E")),
+            value(CompassPoint::South, tag("S")),
+            value(CompassPoint::West, tag("W")),
+        )),
+    ))
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_letter_octant() {
+        assert_eq!(nom_compass_octant("NE"), Ok(("", CompassOctant::NorthEast)));
+        assert_eq!(nom_compass_octant("N "), Ok((" ", CompassOctant::North)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(CompassOctant::SouthWest.to_string(), "SW");
+    }
+
+    #[test]
+    fn test_compass_point() {
+        assert_eq!(
+            nom_compass_point("NNE 20KT"),
+            Ok((" 20KT", CompassPoint::NorthNorthEast))
+        );
+        assert_eq!(nom_compass_point("NE"), Ok(("", CompassPoint::NorthEast)));
+        assert_eq!(nom_compass_point("W"), Ok(("", CompassPoint::West)));
+        assert_eq!(CompassPoint::WestSouthWest.bearing(), 247.5);
+        assert_eq!(CompassPoint::EastSouthEast.to_string(), "ESE");
+    }
+}
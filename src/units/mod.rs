@@ -1,4 +1,5 @@
 pub mod altitudes;
+pub mod compass;
 pub mod timestamp;
 pub mod track;
 pub mod velocity;
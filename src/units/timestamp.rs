@@ -1,11 +1,11 @@
 use std::fmt::Display;
 
-use jiff::{Zoned, civil::date, tz::TimeZone};
+use jiff::{ToSpan, Zoned, civil::date, tz::TimeZone};
 use nom::{
     IResult, Parser,
     bytes::complete::take,
     character::complete::{char, i8},
-    combinator::map_parser,
+    combinator::{map_opt, map_parser, verify},
     sequence::terminated,
 };
 
@@ -18,6 +18,68 @@ impl Timestamp {
     pub fn new(timestamp: Zoned) -> Self {
         Timestamp { timestamp }
     }
+
+    pub fn time(&self) -> &Zoned {
+        &self.timestamp
+    }
+
+    /// Resolves an `hhmm` group to the first matching time at or after this
+    /// timestamp. `2400` is accepted and means midnight at the end of the day.
+    pub(crate) fn next_time_of_day(&self, hour: i8, minute: i8) -> Zoned {
+        let (days, hour) = if hour == 24 { (1, 0) } else { (0, hour) };
+        let mut time = self
+            .timestamp
+            .date()
+            .at(hour, minute, 0, 0)
+            .to_zoned(self.timestamp.time_zone().clone())
+            .unwrap();
+        if days == 1 || time < self.timestamp {
+            time = time.tomorrow().unwrap();
+        }
+        time
+    }
+
+    /// Resolves an `hhmm` group, or an `mm` group within the hour of this
+    /// timestamp, to the latest matching time at or before this timestamp.
+    pub(crate) fn previous_time(&self, hour: Option<i8>, minute: i8) -> Zoned {
+        let time = self
+            .timestamp
+            .date()
+            .at(hour.unwrap_or(self.timestamp.hour()), minute, 0, 0)
+            .to_zoned(self.timestamp.time_zone().clone())
+            .unwrap();
+        match (time > self.timestamp, hour) {
+            (false, _) => time,
+            (true, Some(_)) => time.yesterday().unwrap(),
+            (true, None) => time
+                .checked_sub(jiff::SignedDuration::from_hours(1))
+                .unwrap(),
+        }
+    }
+
+    /// Resolves a `ddhh(mm)` group to the matching time closest to this
+    /// timestamp, looking at the previous, current and next month. Hour `24`
+    /// means midnight at the end of the day. Returns `None` if the day does
+    /// not exist in any of those months.
+    pub(crate) fn nearest_day_time(&self, day: i8, hour: i8, minute: i8) -> Option<Zoned> {
+        let (days, hour) = if hour == 24 { (1, 0) } else { (0, hour) };
+        let month = self.timestamp.date().first_of_month();
+        [-1, 0, 1]
+            .into_iter()
+            .filter_map(|offset| {
+                let month = month.checked_add(offset.months()).ok()?;
+                let time = date(month.year(), month.month(), 1)
+                    .with()
+                    .day(day)
+                    .build()
+                    .ok()?
+                    .at(hour, minute, 0, 0)
+                    .to_zoned(self.timestamp.time_zone().clone())
+                    .ok()?;
+                time.checked_add(days.days()).ok()
+            })
+            .min_by_key(|time| self.timestamp.duration_until(time).abs())
+    }
 }
 
 impl Display for Timestamp {
@@ -26,62 +88,111 @@ impl Display for Timestamp {
     }
 }
 
-fn parse_double_digit(input: &str) -> IResult<&str, i8> {
+pub(crate) fn parse_double_digit(input: &str) -> IResult<&str, i8> {
     map_parser(take(2usize), i8).parse(input)
 }
 
-fn get_date_form_fields(cmp: &Zoned, day: i8, hour: i8, minute: i8) -> Zoned {
+/// `hhmm`. Hour `24` is only accepted as `2400`, midnight at the end of the
+/// day.
+pub(crate) fn nom_hour_minute(input: &str) -> IResult<&str, (i8, i8)> {
+    verify(
+        (parse_double_digit, parse_double_digit),
+        |&(hour, minute)| {
+            ((0..24).contains(&hour) && (0..60).contains(&minute)) || (hour, minute) == (24, 0)
+        },
+    )
+    .parse(input)
+}
+
+/// `ddhhmm`, resolved to the matching time closest to `timestamp`.
+pub(crate) fn nom_day_time<'a>(input: &'a str, timestamp: &Timestamp) -> IResult<&'a str, Zoned> {
+    map_opt(
+        (parse_double_digit, nom_hour_minute),
+        |(day, (hour, minute))| timestamp.nearest_day_time(day, hour, minute),
+    )
+    .parse(input)
+}
+
+/// `None` if the day does not exist in the resolved month.
+fn get_date_form_fields(cmp: &Zoned, day: i8, hour: i8, minute: i8) -> Option<Zoned> {
     let ordering = day
         .cmp(&cmp.day())
         .then_with(|| hour.cmp(&cmp.hour()))
         .then_with(|| minute.cmp(&cmp.minute()));
     let month = if ordering == std::cmp::Ordering::Less {
-        cmp.month()
+        cmp.date().first_of_month()
     } else {
-        cmp.month() - 1
+        cmp.date().first_of_month().checked_sub(1.month()).ok()?
     };
-    date(cmp.year(), month, day)
+    date(month.year(), month.month(), 1)
+        .with()
+        .day(day)
+        .build()
+        .ok()?
         .at(hour, minute, 0, 0)
         .to_zoned(cmp.time_zone().clone())
-        .unwrap()
+        .ok()
 }
 
-pub(crate) fn nom_metar_timestamp(input: &str) -> IResult<&str, Timestamp> {
+/// The current UTC time an hour ahead, the reference for reports parsed
+/// without one. The margin allows for a clock that runs slightly slow.
+pub(crate) fn reference_now() -> Zoned {
     let mut now = Zoned::now().with_time_zone(TimeZone::UTC);
     now += jiff::SignedDuration::from_hours(1);
-    nom_metar_timestamp_with_zone(input, &mut now)
+    now
+}
+
+pub(crate) fn nom_metar_timestamp(input: &str) -> IResult<&str, Timestamp> {
+    nom_metar_timestamp_with_zone(input, &mut reference_now())
+}
+
+/// Like [`nom_metar_timestamp`], resolving the day against `reference`
+/// instead of the current time.
+pub(crate) fn nom_metar_timestamp_at<'a>(
+    input: &'a str,
+    reference: &Timestamp,
+) -> IResult<&'a str, Timestamp> {
+    nom_metar_timestamp_with_zone(input, &mut reference.time().clone())
 }
 
 fn nom_metar_timestamp_with_zone<'a>(
     input: &'a str,
     refernce_time: &mut Zoned,
 ) -> IResult<&'a str, Timestamp> {
-    let (rest, fields) = terminated(
-        (parse_double_digit, parse_double_digit, parse_double_digit),
+    let (rest, (day, hour, minute)) = terminated(
+        verify(
+            (parse_double_digit, parse_double_digit, parse_double_digit),
+            |(day, hour, minute)| {
+                (1..=31).contains(day) && (0..24).contains(hour) && (0..60).contains(minute)
+            },
+        ),
         char('Z'),
     )
     .parse(input)?;
-    let (day, hour, minute) = fields;
     *refernce_time += jiff::SignedDuration::from_hours(1);
-    let timestamp = get_date_form_fields(&refernce_time, day, hour, minute);
+    let timestamp = get_date_form_fields(refernce_time, day, hour, minute).ok_or_else(|| {
+        nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::MapOpt))
+    })?;
     Ok((rest, Timestamp { timestamp }))
 }
 
+/// UTC time for use in tests.
+#[cfg(test)]
+pub(crate) fn test_time(year: i16, month: i8, day: i8, hour: i8, minute: i8) -> Zoned {
+    date(year, month, day)
+        .at(hour, minute, 0, 0)
+        .to_zoned(TimeZone::UTC)
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn make_test_timestamp() -> Zoned {
-        date(2025, 06, 28)
-            .at(16, 0, 0, 0)
-            .to_zoned(TimeZone::UTC)
-            .unwrap()
-    }
-
     #[test]
     fn test_metar_timestamp() {
         let input = "281250Z";
-        let mut day = make_test_timestamp();
+        let mut day = test_time(2025, 6, 28, 16, 0);
         let expected = Timestamp::new(
             day.date()
                 .at(12, 50, 0, 0)
@@ -97,7 +208,7 @@ mod tests {
     #[test]
     fn test_metar_day_before() {
         let input = "271250Z";
-        let mut day = make_test_timestamp();
+        let mut day = test_time(2025, 6, 28, 16, 0);
         let expected = Timestamp::new(
             day.date()
                 .yesterday()
@@ -115,9 +226,9 @@ mod tests {
     #[test]
     fn test_metar_last_month() {
         let input = "291250Z";
-        let mut day = make_test_timestamp();
+        let mut day = test_time(2025, 6, 28, 16, 0);
         let expected = Timestamp::new(
-            date(2025, 05, 29)
+            date(2025, 5, 29)
                 .at(12, 50, 0, 0)
                 .to_zoned(day.time_zone().clone())
                 .unwrap(),
@@ -128,9 +239,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_next_time_of_day() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 28, 16, 0));
+        let same_day = test_time(2025, 6, 28, 17, 30);
+        assert_eq!(timestamp.next_time_of_day(17, 30), same_day);
+        let next_day = test_time(2025, 6, 29, 1, 0);
+        assert_eq!(timestamp.next_time_of_day(1, 0), next_day);
+        let midnight = test_time(2025, 6, 29, 0, 0);
+        assert_eq!(timestamp.next_time_of_day(24, 0), midnight);
+    }
+
+    #[test]
+    fn test_previous_time() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 28, 16, 0));
+        let earlier = test_time(2025, 6, 28, 15, 5);
+        assert_eq!(timestamp.previous_time(Some(15), 5), earlier);
+        let yesterday = test_time(2025, 6, 27, 19, 55);
+        assert_eq!(timestamp.previous_time(Some(19), 55), yesterday);
+        let previous_hour = test_time(2025, 6, 28, 15, 30);
+        assert_eq!(timestamp.previous_time(None, 30), previous_hour);
+        assert_eq!(
+            timestamp.previous_time(None, 0),
+            test_time(2025, 6, 28, 16, 0)
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestamp() {
+        let mut reference = test_time(2025, 7, 1, 10, 0);
+        for input in ["999999Z", "002000Z", "012400Z", "011260Z", "311200Z"] {
+            assert!(
+                nom_metar_timestamp_with_zone(input, &mut reference).is_err(),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_metar_last_year() {
+        let mut reference = test_time(2025, 1, 2, 10, 0);
+        let (_, timestamp) = nom_metar_timestamp_with_zone("312350Z", &mut reference).unwrap();
+        assert_eq!(timestamp.time(), &test_time(2024, 12, 31, 23, 50));
+    }
+
+    #[test]
+    fn test_nearest_day_time() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 30, 17, 0));
+        assert_eq!(
+            timestamp.nearest_day_time(1, 6, 0),
+            Some(test_time(2025, 7, 1, 6, 0))
+        );
+        assert_eq!(
+            timestamp.nearest_day_time(30, 24, 0),
+            Some(test_time(2025, 7, 1, 0, 0))
+        );
+        assert_eq!(
+            timestamp.nearest_day_time(29, 12, 0),
+            Some(test_time(2025, 6, 29, 12, 0))
+        );
+        assert_eq!(timestamp.nearest_day_time(32, 0, 0), None);
+    }
+
+    #[test]
+    fn test_day_time() {
+        let timestamp = Timestamp::new(test_time(2025, 6, 17, 12, 0));
+        assert_eq!(
+            nom_day_time("172400", &timestamp),
+            Ok(("", test_time(2025, 6, 18, 0, 0)))
+        );
+        assert!(nom_day_time("172430", &timestamp).is_err());
+        assert!(nom_day_time("171260", &timestamp).is_err());
+    }
+
     #[test]
     fn test_display() {
-        let mut r = make_test_timestamp();
+        let mut r = test_time(2025, 6, 28, 16, 0);
         let timestamp = nom_metar_timestamp_with_zone("281220Z", &mut r).unwrap().1;
         let formatted = format!("{}", timestamp);
         assert_eq!(formatted, "281220Z");
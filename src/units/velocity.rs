@@ -1,7 +1,11 @@
 use std::fmt::{self, Display};
 
 use nom::{
-    Parser, branch::alt, bytes::complete::tag, character::complete::u32, combinator::opt,
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{char, u32},
+    combinator::{all_consuming, map_parser, opt},
     sequence::preceded,
 };
 
@@ -10,7 +14,11 @@ use crate::optional_data::OptionalNumber;
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct WindVelocity {
     pub velocity: OptionalNumber<2>,
+    /// `P` prefix: the speed is above the highest value that can be reported,
+    /// e.g. `P99MPS`.
+    pub velocity_above_maximum: bool,
     pub gust: Option<OptionalNumber<2>>,
+    pub gust_above_maximum: bool,
     pub unit: VelocityUnit,
 }
 
@@ -18,6 +26,7 @@ pub struct WindVelocity {
 pub enum VelocityUnit {
     MetersPerSecond,
     Knots,
+    KilometersPerHour,
 }
 
 impl WindVelocity {
@@ -26,13 +35,27 @@ impl WindVelocity {
     }
 }
 
+/// Speeds are two digits, or three digits for 100 units or more.
+fn nom_speed(input: &str) -> IResult<&str, (bool, OptionalNumber<2>)> {
+    (
+        opt(char('P')),
+        OptionalNumber::optional_field(map_parser(
+            take_while_m_n(2, 3, |c: char| c.is_ascii_digit()),
+            all_consuming(u32),
+        )),
+    )
+        .map(|(above_maximum, speed)| (above_maximum.is_some(), speed))
+        .parse(input)
+}
+
 pub(crate) fn nom_velocity(input: &str) -> nom::IResult<&str, WindVelocity> {
-    let (rest, v) = OptionalNumber::optional_field(u32).parse(input)?;
-    let (rest, gust) = opt(preceded(tag("G"), OptionalNumber::optional_field(u32))).parse(rest)?;
-    let (rest, unit) = alt((tag("KT"), tag("MPS"))).parse(rest)?;
+    let (rest, (velocity_above_maximum, v)) = nom_speed(input)?;
+    let (rest, gust) = opt(preceded(tag("G"), nom_speed)).parse(rest)?;
+    let (rest, unit) = alt((tag("KT"), tag("MPS"), tag("KMH"))).parse(rest)?;
     let unit = match unit {
         "KT" => VelocityUnit::Knots,
         "MPS" => VelocityUnit::MetersPerSecond,
+        "KMH" => VelocityUnit::KilometersPerHour,
         _ => {
             return Err(nom::Err::Error(nom::error::Error::new(
                 input,
@@ -44,17 +67,26 @@ pub(crate) fn nom_velocity(input: &str) -> nom::IResult<&str, WindVelocity> {
         rest,
         WindVelocity {
             velocity: v,
+            velocity_above_maximum,
             unit,
-            gust,
+            gust: gust.map(|(_, gust)| gust),
+            gust_above_maximum: gust.is_some_and(|(above_maximum, _)| above_maximum),
         },
     ))
 }
 
 impl Display for WindVelocity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.velocity_above_maximum {
+            write!(f, "P")?;
+        }
         write!(f, "{}", self.velocity)?;
         if let Some(gust) = self.gust {
-            write!(f, "G{}", gust)?;
+            write!(f, "G")?;
+            if self.gust_above_maximum {
+                write!(f, "P")?;
+            }
+            write!(f, "{}", gust)?;
         }
         write!(
             f,
@@ -62,6 +94,7 @@ impl Display for WindVelocity {
             match self.unit {
                 VelocityUnit::Knots => "KT",
                 VelocityUnit::MetersPerSecond => "MPS",
+                VelocityUnit::KilometersPerHour => "KMH",
             }
         )
     }
@@ -87,8 +120,10 @@ mod tests {
             "10KT",
             WindVelocity {
                 velocity: Data(10),
+                velocity_above_maximum: false,
                 unit: VelocityUnit::Knots,
                 gust: None,
+                gust_above_maximum: false,
             },
         );
     }
@@ -99,8 +134,10 @@ mod tests {
             "10MPS",
             WindVelocity {
                 velocity: Data(10),
+                velocity_above_maximum: false,
                 unit: VelocityUnit::MetersPerSecond,
                 gust: None,
+                gust_above_maximum: false,
             },
         );
     }
@@ -115,8 +152,10 @@ mod tests {
     fn test_display_kt() {
         let v = WindVelocity {
             velocity: Data(10),
+            velocity_above_maximum: false,
             unit: VelocityUnit::Knots,
             gust: None,
+            gust_above_maximum: false,
         };
         assert_eq!(v.to_string(), "10KT");
     }
@@ -125,8 +164,10 @@ mod tests {
     fn test_display_mps() {
         let v = WindVelocity {
             velocity: Data(10),
+            velocity_above_maximum: false,
             unit: VelocityUnit::MetersPerSecond,
             gust: None,
+            gust_above_maximum: false,
         };
         assert_eq!(v.to_string(), "10MPS");
     }
@@ -141,10 +182,57 @@ mod tests {
                 "",
                 WindVelocity {
                     velocity: Data(13),
+                    velocity_above_maximum: false,
                     gust: Some(Data(19)),
+                    gust_above_maximum: false,
                     unit: VelocityUnit::Knots
                 }
             ))
         );
     }
+
+    #[test]
+    fn test_kmh() {
+        let v = nom_velocity("15KMH").unwrap().1;
+        assert_eq!(v.unit, VelocityUnit::KilometersPerHour);
+        assert_eq!(v.to_string(), "15KMH");
+    }
+
+    #[test]
+    fn test_three_digit_speeds() {
+        let result = nom_velocity("120G150KT");
+        assert_eq!(
+            result,
+            Ok((
+                "",
+                WindVelocity {
+                    velocity: Data(120),
+                    velocity_above_maximum: false,
+                    gust: Some(Data(150)),
+                    gust_above_maximum: false,
+                    unit: VelocityUnit::Knots,
+                }
+            ))
+        );
+        assert_eq!(result.unwrap().1.to_string(), "120G150KT");
+    }
+
+    #[test]
+    fn test_above_maximum() {
+        let v = nom_velocity("P99MPS").unwrap().1;
+        assert!(v.velocity_above_maximum);
+        assert_eq!(v.velocity, Data(99));
+        assert_eq!(v.to_string(), "P99MPS");
+
+        let v = nom_velocity("90GP199KT").unwrap().1;
+        assert!(!v.velocity_above_maximum);
+        assert!(v.gust_above_maximum);
+        assert_eq!(v.get_max_wind_speed(), Some(199));
+        assert_eq!(v.to_string(), "90GP199KT");
+    }
+
+    #[test]
+    fn test_too_many_digits() {
+        assert!(nom_velocity("1200KT").is_err());
+    }
 }
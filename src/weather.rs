@@ -0,0 +1,318 @@
+use std::fmt::{self, Display};
+
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::alphanumeric1,
+    combinator::{not, opt, value, verify},
+    multi::many0,
+    sequence::{preceded, terminated},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PresentWeather {
+    pub intensity: WeatherIntensity,
+    pub descriptor: Option<WeatherDescriptor>,
+    pub phenomena: Vec<WeatherPhenomenon>,
+}
+
+/// Operationally significant weather observed since the previous report (or
+/// the last hour), but not at the time of observation. Reported with a `RE`
+/// prefix and without intensity.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecentWeather {
+    pub descriptor: Option<WeatherDescriptor>,
+    pub phenomena: Vec<WeatherPhenomenon>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherIntensity {
+    Light,
+    Moderate,
+    Heavy,
+    /// Observed within 8 km of the aerodrome, but not at it.
+    Vicinity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherDescriptor {
+    Shallow,
+    Patches,
+    Partial,
+    LowDrifting,
+    Blowing,
+    Showers,
+    Thunderstorm,
+    Freezing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherPhenomenon {
+    Drizzle,
+    Rain,
+    Snow,
+    SnowGrains,
+    IceCrystals,
+    IcePellets,
+    Hail,
+    SmallHail,
+    UnknownPrecipitation,
+    Mist,
+    Fog,
+    Smoke,
+    VolcanicAsh,
+    Dust,
+    Sand,
+    Haze,
+    DustWhirls,
+    Squalls,
+    FunnelCloud,
+    Sandstorm,
+    Duststorm,
+}
+
+impl WeatherIntensity {
+    fn code(&self) -> &'static str {
+        match self {
+            WeatherIntensity::Light => "-",
+            WeatherIntensity::Moderate => "",
+            WeatherIntensity::Heavy => "+",
+            WeatherIntensity::Vicinity => "VC",
+        }
+    }
+}
+
+impl WeatherDescriptor {
+    fn code(&self) -> &'static str {
+        match self {
+            WeatherDescriptor::Shallow => "MI",
+            WeatherDescriptor::Patches => "BC",
+            WeatherDescriptor::Partial => "PR",
+            WeatherDescriptor::LowDrifting => "DR",
+            WeatherDescriptor::Blowing => "BL",
+            WeatherDescriptor::Showers => "SH",
+            WeatherDescriptor::Thunderstorm => "TS",
+            WeatherDescriptor::Freezing => "FZ",
+        }
+    }
+}
+
+impl WeatherPhenomenon {
+    fn code(&self) -> &'static str {
+        match self {
+            WeatherPhenomenon::Drizzle => "DZ",
+            WeatherPhenomenon::Rain => "RA",
+            WeatherPhenomenon::Snow => "SN",
+            WeatherPhenomenon::SnowGrains => "SG",
+            WeatherPhenomenon::IceCrystals => "IC",
+            WeatherPhenomenon::IcePellets => "PL",
+            WeatherPhenomenon::Hail => "GR",
+            WeatherPhenomenon::SmallHail => "GS",
+            WeatherPhenomenon::UnknownPrecipitation => "UP",
+            WeatherPhenomenon::Mist => "BR",
+            WeatherPhenomenon::Fog => "FG",
+            WeatherPhenomenon::Smoke => "FU",
+            WeatherPhenomenon::VolcanicAsh => "VA",
+            WeatherPhenomenon::Dust => "DU",
+            WeatherPhenomenon::Sand => "SA",
+            WeatherPhenomenon::Haze => "HZ",
+            WeatherPhenomenon::DustWhirls => "PO",
+            WeatherPhenomenon::Squalls => "SQ",
+            WeatherPhenomenon::FunnelCloud => "FC",
+            WeatherPhenomenon::Sandstorm => "SS",
+            WeatherPhenomenon::Duststorm => "DS",
+        }
+    }
+}
+
+impl Display for PresentWeather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.intensity.code())?;
+        if let Some(descriptor) = self.descriptor {
+            write!(f, "{}", descriptor.code())?;
+        }
+        for phenomenon in &self.phenomena {
+            write!(f, "{}", phenomenon.code())?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for RecentWeather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RE")?;
+        if let Some(descriptor) = self.descriptor {
+            write!(f, "{}", descriptor.code())?;
+        }
+        for phenomenon in &self.phenomena {
+            write!(f, "{}", phenomenon.code())?;
+        }
+        Ok(())
+    }
+}
+
+fn nom_weather_intensity(input: &str) -> IResult<&str, WeatherIntensity> {
+    alt((
+        value(WeatherIntensity::Light, tag("-")),
+        value(WeatherIntensity::Heavy, tag("+")),
+        value(WeatherIntensity::Vicinity, tag("VC")),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_weather_descriptor(input: &str) -> IResult<&str, WeatherDescriptor> {
+    alt((
+        value(WeatherDescriptor::Shallow, tag("MI")),
+        value(WeatherDescriptor::Patches, tag("BC")),
+        value(WeatherDescriptor::Partial, tag("PR")),
+        value(WeatherDescriptor::LowDrifting, tag("DR")),
+        value(WeatherDescriptor::Blowing, tag("BL")),
+        value(WeatherDescriptor::Showers, tag("SH")),
+        value(WeatherDescriptor::Thunderstorm, tag("TS")),
+        value(WeatherDescriptor::Freezing, tag("FZ")),
+    ))
+    .parse(input)
+}
+
+pub(crate) fn nom_weather_phenomenon(input: &str) -> IResult<&str, WeatherPhenomenon> {
+    alt((
+        alt((
+            value(WeatherPhenomenon::Drizzle, tag("DZ")),
+            value(WeatherPhenomenon::Rain, tag("RA")),
+            value(WeatherPhenomenon::Snow, tag("SN")),
+            value(WeatherPhenomenon::SnowGrains, tag("SG")),
+            value(WeatherPhenomenon::IceCrystals, tag("IC")),
+            value(WeatherPhenomenon::IcePellets, tag("PL")),
+            value(WeatherPhenomenon::Hail, tag("GR")),
+            value(WeatherPhenomenon::SmallHail, tag("GS")),
+            value(WeatherPhenomenon::UnknownPrecipitation, tag("UP")),
+        )),
+        alt((
+            value(WeatherPhenomenon::Mist, tag("BR")),
+            value(WeatherPhenomenon::Fog, tag("FG")),
+            value(WeatherPhenomenon::Smoke, tag("FU")),
+            value(WeatherPhenomenon::VolcanicAsh, tag("VA")),
+            value(WeatherPhenomenon::Dust, tag("DU")),
+            value(WeatherPhenomenon::Sand, tag("SA")),
+            value(WeatherPhenomenon::Haze, tag("HZ")),
+        )),
+        alt((
+            value(WeatherPhenomenon::DustWhirls, tag("PO")),
+            value(WeatherPhenomenon::Squalls, tag("SQ")),
+            value(WeatherPhenomenon::FunnelCloud, tag("FC")),
+            value(WeatherPhenomenon::Sandstorm, tag("SS")),
+            value(WeatherPhenomenon::Duststorm, tag("DS")),
+        )),
+    ))
+    .parse(input)
+}
+
+/// A descriptor may stand on its own (`TS`, `VCSH`), otherwise at least one
+/// phenomenon is required. The group must end at a group boundary so that
+/// e.g. `SCT015` is never mistaken for weather.
+fn nom_weather_body(
+    input: &str,
+) -> IResult<&str, (Option<WeatherDescriptor>, Vec<WeatherPhenomenon>)> {
+    terminated(
+        verify(
+            (opt(nom_weather_descriptor), many0(nom_weather_phenomenon)),
+            |(descriptor, phenomena)| {
+                !phenomena.is_empty()
+                    || matches!(
+                        descriptor,
+                        Some(WeatherDescriptor::Thunderstorm | WeatherDescriptor::Showers)
+                    )
+            },
+        ),
+        not(alphanumeric1),
+    )
+    .parse(input)
+}
+
+pub(crate) fn nom_present_weather(input: &str) -> IResult<&str, PresentWeather> {
+    (opt(nom_weather_intensity), nom_weather_body)
+        .map(|(intensity, (descriptor, phenomena))| PresentWeather {
+            intensity: intensity.unwrap_or(WeatherIntensity::Moderate),
+            descriptor,
+            phenomena,
+        })
+        .parse(input)
+}
+
+pub(crate) fn nom_recent_weather(input: &str) -> IResult<&str, RecentWeather> {
+    preceded(tag("RE"), nom_weather_body)
+        .map(|(descriptor, phenomena)| RecentWeather {
+            descriptor,
+            phenomena,
+        })
+        .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vicinity_showers() {
+        let expected = PresentWeather {
+            intensity: WeatherIntensity::Vicinity,
+            descriptor: Some(WeatherDescriptor::Showers),
+            phenomena: vec![],
+        };
+        assert_eq!(
+            nom_present_weather("VCSH FEW019"),
+            Ok((" FEW019", expected))
+        );
+    }
+
+    #[test]
+    fn test_multiple_phenomena() {
+        let expected = PresentWeather {
+            intensity: WeatherIntensity::Moderate,
+            descriptor: None,
+            phenomena: vec![WeatherPhenomenon::Rain, WeatherPhenomenon::Drizzle],
+        };
+        assert_eq!(nom_present_weather("RADZ"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_heavy_thunderstorm() {
+        let expected = PresentWeather {
+            intensity: WeatherIntensity::Heavy,
+            descriptor: Some(WeatherDescriptor::Thunderstorm),
+            phenomena: vec![WeatherPhenomenon::Rain, WeatherPhenomenon::SmallHail],
+        };
+        assert_eq!(nom_present_weather("+TSRAGS"), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_not_weather() {
+        assert!(nom_present_weather("SCT015").is_err());
+        assert!(nom_present_weather("FZ").is_err());
+        assert!(nom_present_weather("RAIN").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let weather = nom_present_weather("-SHRA").unwrap().1;
+        assert_eq!(weather.to_string(), "-SHRA");
+    }
+
+    #[test]
+    fn test_recent_freezing_rain() {
+        let expected = RecentWeather {
+            descriptor: Some(WeatherDescriptor::Freezing),
+            phenomena: vec![WeatherPhenomenon::Rain],
+        };
+        assert_eq!(nom_recent_weather("REFZRA RMK"), Ok((" RMK", expected)));
+    }
+
+    #[test]
+    fn test_recent_thunderstorm() {
+        let weather = nom_recent_weather("RETS").unwrap().1;
+        assert_eq!(weather.descriptor, Some(WeatherDescriptor::Thunderstorm));
+        assert!(weather.phenomena.is_empty());
+        assert_eq!(weather.to_string(), "RETS");
+    }
+}
@@ -73,7 +73,9 @@ mod tests {
             dir: WindDirection::Heading(Track(Data(210))),
             speed: WindVelocity {
                 velocity: Data(7),
+                velocity_above_maximum: false,
                 gust: Some(Data(17)),
+                gust_above_maximum: false,
                 unit: VelocityUnit::Knots,
             },
             varying: Some((Track(Data(160)), Track(Data(270)))),
@@ -88,7 +90,9 @@ mod tests {
             dir: WindDirection::Variable,
             speed: WindVelocity {
                 velocity: Data(3),
+                velocity_above_maximum: false,
                 gust: None,
+                gust_above_maximum: false,
                 unit: VelocityUnit::MetersPerSecond,
             },
             varying: None,
@@ -103,7 +107,9 @@ mod tests {
             dir: WindDirection::Variable,
             speed: WindVelocity {
                 velocity: Data(3),
+                velocity_above_maximum: false,
                 gust: None,
+                gust_above_maximum: false,
                 unit: VelocityUnit::MetersPerSecond,
             },
             varying: Some((Track(Data(160)), Track(Data(270)))),
@@ -118,7 +124,9 @@ mod tests {
             dir: WindDirection::Heading(Track(Data(270))),
             speed: WindVelocity {
                 velocity: Data(10),
+                velocity_above_maximum: false,
                 gust: None,
+                gust_above_maximum: false,
                 unit: VelocityUnit::Knots,
             },
             varying: None,
@@ -133,7 +141,26 @@ mod tests {
             dir: WindDirection::Heading(Track(Data(0))),
             speed: WindVelocity {
                 velocity: Data(0),
+                velocity_above_maximum: false,
                 gust: None,
+                gust_above_maximum: false,
+                unit: VelocityUnit::Knots,
+            },
+            varying: None,
+        };
+        setup_test(t, expected);
+    }
+
+    #[test]
+    fn test_hurricane_force() {
+        let t = "250120G150KT";
+        let expected = Wind {
+            dir: WindDirection::Heading(Track(Data(250))),
+            speed: WindVelocity {
+                velocity: Data(120),
+                velocity_above_maximum: false,
+                gust: Some(Data(150)),
+                gust_above_maximum: false,
                 unit: VelocityUnit::Knots,
             },
             varying: None,